@@ -1,5 +1,20 @@
-use crate::{ray::Ray, vec::Vec3};
+use crate::{
+    float,
+    ray::Ray,
+    stats::{self, Counter},
+    vec::{Affine3, Vec3},
+};
 
+/// An axis-aligned bounding box between two corner points.
+///
+/// ```
+/// use raytracing_rust::{aabb::AABB, ray::Ray, vec::Vec3};
+///
+/// let bbox = AABB::new(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0));
+/// let r = Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0), 0.0);
+/// assert!(bbox.hit(&r, 0.0, f64::INFINITY));
+/// assert!(!bbox.hit(&r, 0.0, 1.0));
+/// ```
 #[derive(Clone, Copy, Debug)]
 pub struct AABB {
     min: Vec3,
@@ -15,12 +30,12 @@ impl AABB {
         let small = Vec3::new(
             box0.min().x.min(box1.min().x),
             box0.min().y.min(box1.min().y),
-            box0.min().y.min(box1.min().z),
+            box0.min().z.min(box1.min().z),
         );
         let large = Vec3::new(
             box0.max().x.max(box1.max().x),
             box0.max().y.max(box1.max().y),
-            box0.max().y.max(box1.max().z),
+            box0.max().z.max(box1.max().z),
         );
 
         Self::new(small, large)
@@ -34,11 +49,54 @@ impl AABB {
         self.max
     }
 
+    /// Get the eight corner points of the box.
+    pub fn corners(&self) -> [Vec3; 8] {
+        [0, 1, 2, 3, 4, 5, 6, 7].map(|i| {
+            let pick = |bit: usize, axis: usize| {
+                if i & bit != 0 {
+                    self.max[axis]
+                } else {
+                    self.min[axis]
+                }
+            };
+            Vec3::new(pick(1, 0), pick(2, 1), pick(4, 2))
+        })
+    }
+
+    /// Get the box bounding a set of points.
+    pub fn from_points(points: impl IntoIterator<Item = Vec3>) -> Self {
+        let mut min = Vec3::splat(f64::INFINITY);
+        let mut max = Vec3::splat(f64::NEG_INFINITY);
+        for p in points {
+            min = min.min(p);
+            max = max.max(p);
+        }
+        Self::new(min, max)
+    }
+
+    /// Get the box bounding all eight corners of this box after applying `transform`.
+    pub fn transformed(&self, transform: &Affine3) -> Self {
+        Self::from_points(self.corners().map(|p| transform.transform_point3(p)))
+    }
+
     /// Determine if the ray hits the bounding box between contour positions `s_min` and `s_max`.
     pub fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> bool {
+        stats::increment(Counter::AABBTests);
+        self.clip(r, s_min, s_max).is_some()
+    }
+
+    /// Get the part of the contour interval from `s_min` to `s_max` where the ray is inside the box.
+    ///
+    /// The far side of each slab is pushed out by its rounding error, so rays are never
+    /// lost through the edges of a box, and flat boxes around planar objects still get hit.
+    pub fn clip(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<(f64, f64)> {
         let ray_origin = r.origin();
         let ray_direction = r.direction();
 
+        // Narrow the interval slab by slab, so the ray must be inside all three at once
+        let mut s_min = s_min;
+        let mut s_max = s_max;
+
         for i in 0..3 {
             let inv_d = 1.0 / ray_direction[i];
 
@@ -46,15 +104,16 @@ impl AABB {
             let s1 = (self.max[i] - ray_origin[i]) * inv_d;
 
             let (s0, s1) = if inv_d < 0.0 { (s1, s0) } else { (s0, s1) };
+            let s1 = s1 + 2.0 * float::gamma(3) * s1.abs();
 
-            let s_min = s_min.max(s0);
-            let s_max = s_max.min(s1);
+            s_min = s_min.max(s0);
+            s_max = s_max.min(s1);
 
-            if s_max <= s_min {
-                return false;
+            if s_max < s_min {
+                return None;
             }
         }
 
-        true
+        Some((s_min, s_max))
     }
 }
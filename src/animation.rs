@@ -0,0 +1,255 @@
+use std::{
+    fs,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use image::ImageResult;
+
+use crate::{
+    camera::ViewOptions,
+    hittable::HittableList,
+    render::Renderer,
+    vec::{Quat, Vec3},
+};
+
+/// Trait for values that can be linearly interpolated between keyframes.
+pub trait Lerp {
+    fn lerp(a: &Self, b: &Self, t: f64) -> Self;
+}
+
+impl Lerp for f64 {
+    fn lerp(a: &Self, b: &Self, t: f64) -> Self {
+        a + t * (b - a)
+    }
+}
+
+impl Lerp for Vec3 {
+    fn lerp(a: &Self, b: &Self, t: f64) -> Self {
+        *a + t * (*b - *a)
+    }
+}
+
+impl Lerp for Quat {
+    /// Spherically interpolate along the shorter arc between the two rotations.
+    fn lerp(a: &Self, b: &Self, t: f64) -> Self {
+        let (a, mut b) = (*a, *b);
+        let mut dot = a.dot(b);
+        if dot < 0.0 {
+            b = -b;
+            dot = -dot;
+        }
+
+        // Nearly equal rotations fall back to a normalized linear blend
+        if dot > 0.9995 {
+            return (a + (b - a) * t).normalize();
+        }
+
+        let theta = dot.min(1.0).acos();
+        let (wa, wb) = (((1.0 - t) * theta).sin(), (t * theta).sin());
+        ((a * wa + b * wb) * (1.0 / theta.sin())).normalize()
+    }
+}
+
+/// A sequence of values keyed at points in time.
+///
+/// Values between two keys are linearly interpolated,
+/// and values outside the keyed range are held at the first or last key.
+#[derive(Clone, Debug)]
+pub struct Keyframes<T> {
+    keys: Vec<(f64, T)>,
+}
+
+impl<T: Lerp + Clone> Keyframes<T> {
+    /// Create a track with a single key of `value` at time zero.
+    pub fn new(value: T) -> Self {
+        Self { keys: vec![(0.0, value)] }
+    }
+
+    /// Add a key at `time`, replacing any existing key at the same time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `time` is not finite, as the keys could not be kept in order.
+    pub fn with_key(mut self, time: f64, value: T) -> Self {
+        assert!(time.is_finite(), "Key times must be finite, but got {}.", time);
+        match self.keys.binary_search_by(|(t, _)| t.total_cmp(&time)) {
+            Ok(idx) => self.keys[idx] = (time, value),
+            Err(idx) => self.keys.insert(idx, (time, value)),
+        }
+        self
+    }
+
+    /// Get the times of the keys in order.
+    pub fn key_times(&self) -> impl Iterator<Item = f64> + '_ {
+        self.keys.iter().map(|(t, _)| *t)
+    }
+
+    /// Get the interpolated value of the track at `time`.
+    ///
+    /// ```
+    /// use raytracing_rust::animation::Keyframes;
+    ///
+    /// let track = Keyframes::new(0.0).with_key(2.0, 10.0);
+    /// assert_eq!(track.value_at(1.0), 5.0);
+    /// assert_eq!(track.value_at(3.0), 10.0);
+    /// ```
+    pub fn value_at(&self, time: f64) -> T {
+        let idx = self.keys.partition_point(|(t, _)| *t <= time);
+        if idx == 0 {
+            return self.keys[0].1.clone();
+        }
+        if idx == self.keys.len() {
+            return self.keys[idx - 1].1.clone();
+        }
+
+        let (t0, v0) = &self.keys[idx - 1];
+        let (t1, v1) = &self.keys[idx];
+        T::lerp(v0, v1, (time - t0) / (t1 - t0))
+    }
+}
+
+/// Keyframed camera parameters for an animation.
+#[derive(Clone, Debug)]
+pub struct CameraTrack {
+    pub lookfrom: Keyframes<Vec3>,
+    pub lookat: Keyframes<Vec3>,
+    pub vfov: Keyframes<f64>,
+    pub focus_dist: Keyframes<f64>,
+}
+
+impl CameraTrack {
+    /// Create a track holding the camera parameters of `opts` constant for all time.
+    pub fn from_options(opts: &ViewOptions) -> Self {
+        Self {
+            lookfrom: Keyframes::new(opts.lookfrom),
+            lookat: Keyframes::new(opts.lookat),
+            vfov: Keyframes::new(opts.vfov),
+            focus_dist: Keyframes::new(opts.focus_dist),
+        }
+    }
+
+    pub fn with_lookfrom_key(mut self, time: f64, lookfrom: Vec3) -> Self {
+        self.lookfrom = self.lookfrom.with_key(time, lookfrom);
+        self
+    }
+
+    pub fn with_lookat_key(mut self, time: f64, lookat: Vec3) -> Self {
+        self.lookat = self.lookat.with_key(time, lookat);
+        self
+    }
+
+    pub fn with_vfov_key(mut self, time: f64, vfov: f64) -> Self {
+        self.vfov = self.vfov.with_key(time, vfov);
+        self
+    }
+
+    pub fn with_focus_dist_key(mut self, time: f64, focus_dist: f64) -> Self {
+        self.focus_dist = self.focus_dist.with_key(time, focus_dist);
+        self
+    }
+
+    /// Apply the camera parameters at `time` on top of the base view options.
+    pub fn view_at(&self, opts: &ViewOptions, time: f64) -> ViewOptions {
+        opts.clone()
+            .with_lookfrom(self.lookfrom.value_at(time))
+            .with_lookat(self.lookat.value_at(time))
+            .with_vfov(self.vfov.value_at(time))
+            .with_focus_dist(self.focus_dist.value_at(time))
+    }
+}
+
+/// Progress of an animation, reported for each frame before it is rendered or skipped.
+#[derive(Clone, Copy, Debug)]
+pub enum FrameEvent<'a> {
+    /// The frame's image already exists at `path`, so the frame is skipped.
+    Skipped { frame: usize, path: &'a Path },
+    /// The frame is about to be rendered into `path`.
+    Rendering { frame: usize, path: &'a Path },
+}
+
+/// A sequence of frames rendered with a keyframed camera.
+///
+/// Frame `i` covers the time interval `[i / frame_rate, (i + 1) / frame_rate)`,
+/// and the shutter is open for `shutter_fraction` of that interval starting from its beginning.
+#[derive(Clone, Debug)]
+pub struct Animation {
+    pub frames: usize,
+    pub frame_rate: f64,
+    pub shutter_fraction: f64,
+    pub camera: CameraTrack,
+    pub opts: ViewOptions,
+}
+
+impl Animation {
+    pub fn new(frames: usize, frame_rate: f64, opts: ViewOptions) -> Self {
+        let camera = CameraTrack::from_options(&opts);
+        Self { frames, frame_rate, shutter_fraction: 0.5, camera, opts }
+    }
+
+    pub fn with_shutter_fraction(mut self, shutter_fraction: f64) -> Self {
+        self.shutter_fraction = shutter_fraction;
+        self
+    }
+
+    pub fn with_camera(mut self, camera: CameraTrack) -> Self {
+        self.camera = camera;
+        self
+    }
+
+    /// Get the shutter interval for frame `frame`.
+    pub fn frame_shutter(&self, frame: usize) -> Range<f64> {
+        let time0 = frame as f64 / self.frame_rate;
+        let time1 = time0 + self.shutter_fraction / self.frame_rate;
+        time0..time1
+    }
+
+    /// Get the view options for frame `frame`.
+    ///
+    /// Camera parameters are sampled at the middle of the frame shutter interval.
+    pub fn frame_options(&self, frame: usize) -> ViewOptions {
+        let shutter = self.frame_shutter(frame);
+        let midpoint = 0.5 * (shutter.start + shutter.end);
+        self.camera.view_at(&self.opts, midpoint).with_shutter_time(shutter.start, shutter.end)
+    }
+
+    /// Render every frame of the animation into numbered images `frame_0000.png`, ... in `dir`.
+    ///
+    /// The `scene` closure builds the world for a frame given its shutter interval.
+    /// Frames whose image already exists are skipped, so an interrupted sequence can be resumed.
+    /// Each frame is reported to `on_frame` before it is rendered or skipped.
+    pub fn render_frames<F, P>(
+        &self,
+        dir: impl Into<PathBuf>,
+        scene: F,
+        mut on_frame: P,
+    ) -> ImageResult<()>
+    where
+        F: Fn(Range<f64>) -> HittableList,
+        P: FnMut(FrameEvent),
+    {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+
+        for frame in 0..self.frames {
+            let path = dir.join(format!("frame_{:04}.png", frame));
+            if path.exists() {
+                on_frame(FrameEvent::Skipped { frame, path: &path });
+                continue;
+            }
+
+            on_frame(FrameEvent::Rendering { frame, path: &path });
+            let renderer = Renderer::new(self.frame_options(frame));
+            let world = scene(self.frame_shutter(frame));
+            let fb = renderer.render(&world);
+
+            // Write to a temporary file first so a partially written image is never mistaken
+            // for a finished frame when the sequence is resumed
+            let tmp_path = dir.join(format!("frame_{:04}.tmp.png", frame));
+            fb.save(&tmp_path)?;
+            fs::rename(&tmp_path, &path)?;
+        }
+
+        Ok(())
+    }
+}
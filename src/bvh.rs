@@ -1,4 +1,4 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, time::Instant};
 
 use rand::Rng;
 
@@ -7,27 +7,75 @@ use crate::{
     hit::{Hit, HitRecord},
     hittable::{Hittable, HittableList},
     ray::Ray,
+    stats::{self, Counter},
 };
 
+/// A bounding volume hierarchy over a list of hittable objects.
+///
+/// Each node stores a bounding box surrounding all of its children,
+/// so rays that miss the box can skip the whole subtree.
+/// Objects without a bounding box, such as infinite planes, cannot be placed in the tree,
+/// and are instead kept in a separate list that every ray is tested against.
+///
+/// ```
+/// use raytracing_rust::{
+///     bvh::BVH, color::Color, hit::Hit, hittable::HittableList, material::Lambertian,
+///     plane::Plane, ray::Ray, sphere::Sphere, vec::Vec3,
+/// };
+///
+/// let material = Lambertian::from(Color::new(0.5, 0.5, 0.5));
+/// let mut objects = HittableList::new();
+/// objects.push(Sphere::stationary(Vec3::new(0.0, 1.0, 0.0), 1.0, material.clone()));
+/// objects.push(Plane::new(Vec3::ZERO, Vec3::new(0.0, 1.0, 0.0), material));
+///
+/// let bvh = BVH::new(objects, 0.0, 1.0);
+/// assert!(bvh.bounding_box(0.0, 1.0).is_none());
+///
+/// let r = Ray::new(Vec3::new(5.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0), 0.0);
+/// let rec = bvh.hit(&r, 0.001, f64::INFINITY).unwrap();
+/// assert!(rec.point.y.abs() < 1e-9);
+/// ```
 #[derive(Clone, Debug)]
-pub enum BVH {
+pub struct BVH {
+    root: Option<BVHNode>,
+    unbounded: HittableList,
+}
+
+#[derive(Clone, Debug)]
+enum BVHNode {
     Leaf { object: Box<Hittable>, bbox: AABB },
-    Internal { left: Box<Hittable>, right: Box<Hittable>, bbox: AABB },
+    Internal { left: Box<BVHNode>, right: Box<BVHNode>, bbox: AABB },
 }
 
 impl BVH {
     pub fn new(hittables: HittableList, time0: f64, time1: f64) -> Self {
+        let start = Instant::now();
+
+        let (bounded, unbounded): (Vec<Hittable>, Vec<Hittable>) =
+            hittables.into_iter().partition(|object| object.bounding_box(time0, time1).is_some());
+        let root = (!bounded.is_empty()).then(|| BVHNode::build(bounded, time0, time1));
+
+        stats::record_time(Counter::BVHBuildNanos, start);
+        Self { root, unbounded: HittableList::from(unbounded) }
+    }
+
+    /// Get the objects without a bounding box, which are kept outside of the tree.
+    pub fn unbounded(&self) -> &HittableList {
+        &self.unbounded
+    }
+}
+
+impl BVHNode {
+    fn build(mut objects: Vec<Hittable>, time0: f64, time1: f64) -> Self {
         let mut rng = rand::thread_rng();
         let axis_cmp: usize = rng.gen_range(0..2);
 
-        let mut objects = hittables.objects_owned();
         let comparator =
             |a: &Hittable, b: &Hittable| Self::box_compare(a, b, time0, time1, axis_cmp);
         objects.sort_unstable_by(comparator);
 
         let n = objects.len();
         match n {
-            0 => panic!("Cannot construct a BVH from an empty object list."),
             1 => {
                 let object = objects.pop().unwrap();
                 let bbox = object.bounding_box(time0, time1).unwrap();
@@ -35,28 +83,21 @@ impl BVH {
             }
             _ => {
                 let right_objects: Vec<Hittable> = objects.drain(n / 2..).collect();
-                let right_list = HittableList::from(right_objects);
-                let right = Self::new(right_list, time0, time1);
-
-                let left_list = HittableList::from(objects);
-                let left = Self::new(left_list, time0, time1);
+                let right = Self::build(right_objects, time0, time1);
+                let left = Self::build(objects, time0, time1);
 
                 let bbox = AABB::surrounding_box(left.get_box(), right.get_box());
 
-                Self::Internal { left: Box::new(left.into()), right: Box::new(right.into()), bbox }
+                Self::Internal { left: Box::new(left), right: Box::new(right), bbox }
             }
         }
     }
 
     fn box_compare(a: &Hittable, b: &Hittable, time0: f64, time1: f64, axis: usize) -> Ordering {
-        let a_box = a.bounding_box(time0, time1);
-        let b_box = b.bounding_box(time0, time1);
-
-        if let (Some(ab), Some(bb)) = (a_box, b_box) {
-            return ab.min()[axis].partial_cmp(&bb.min()[axis]).unwrap();
-        };
-
-        panic!("Cannot construct bounding box for objects in BVH node constructor.")
+        // Only bounded objects are placed in the tree
+        let a_box = a.bounding_box(time0, time1).unwrap();
+        let b_box = b.bounding_box(time0, time1).unwrap();
+        a_box.min()[axis].partial_cmp(&b_box.min()[axis]).unwrap_or(Ordering::Equal)
     }
 
     fn get_box(&self) -> AABB {
@@ -65,10 +106,10 @@ impl BVH {
             Self::Internal { bbox, .. } => *bbox,
         }
     }
-}
 
-impl Hit for BVH {
     fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<HitRecord> {
+        stats::increment(Counter::BVHNodeVisits);
+
         match self {
             Self::Leaf { object, .. } => object.hit(r, s_min, s_max),
 
@@ -78,8 +119,7 @@ impl Hit for BVH {
                     return None;
                 }
 
-                // Determine hit record between left and right
-                // Why shift s_max from left hit???
+                // Only look for hits in the right subtree closer than the left hit
                 let left_rec = left.hit(r, s_min, s_max);
                 let left_smax = left_rec.as_ref().map(|rec| rec.s).unwrap_or(s_max);
                 let right_rec = right.hit(r, s_min, left_smax);
@@ -92,8 +132,174 @@ impl Hit for BVH {
             }
         }
     }
+}
+
+impl Hit for BVH {
+    fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<HitRecord> {
+        let tree_rec = self.root.as_ref().and_then(|root| root.hit(r, s_min, s_max));
+        if self.unbounded.is_empty() {
+            return tree_rec;
+        }
+
+        let tree_smax = tree_rec.as_ref().map(|rec| rec.s).unwrap_or(s_max);
+        self.unbounded.hit(r, s_min, tree_smax).or(tree_rec)
+    }
 
     fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<AABB> {
-        Some(self.get_box())
+        if self.unbounded.is_empty() {
+            self.root.as_ref().map(BVHNode::get_box)
+        } else {
+            None
+        }
+    }
+}
+
+/// Maximum number of primitives stored in a leaf of an `IndexBVH`.
+const MAX_LEAF_PRIMITIVES: usize = 4;
+
+#[derive(Clone, Debug)]
+enum IndexBVHNode {
+    Leaf { start: usize, count: usize, bbox: AABB },
+    Internal { left: usize, right: usize, bbox: AABB },
+}
+
+impl IndexBVHNode {
+    fn bbox(&self) -> &AABB {
+        match self {
+            Self::Leaf { bbox, .. } => bbox,
+            Self::Internal { bbox, .. } => bbox,
+        }
+    }
+}
+
+/// A flat bounding volume hierarchy over primitives identified by an index.
+///
+/// Aggregate objects such as meshes keep their primitives in shared buffers
+/// rather than as individual `Hittable`s, and use this tree to find candidate primitives
+/// without cloning any per-primitive data.
+#[derive(Clone, Debug)]
+pub(crate) struct IndexBVH {
+    nodes: Vec<IndexBVHNode>,
+    ids: Vec<usize>,
+}
+
+impl IndexBVH {
+    /// Build a tree over the primitives `ids`, using `bbox_of` to bound each primitive.
+    pub fn new(ids: Vec<usize>, bbox_of: impl Fn(usize) -> AABB) -> Self {
+        let start = Instant::now();
+
+        let bboxes: Vec<AABB> = ids.iter().map(|&id| bbox_of(id)).collect();
+        let mut order: Vec<usize> = (0..ids.len()).collect();
+        let mut nodes = Vec::new();
+        if !order.is_empty() {
+            Self::build(&mut nodes, &bboxes, &mut order, 0);
+        }
+
+        let ids = order.into_iter().map(|i| ids[i]).collect();
+        stats::record_time(Counter::BVHBuildNanos, start);
+
+        Self { nodes, ids }
+    }
+
+    /// Recursively build the subtree for `order[..]`, which starts at position `offset`
+    /// in the final primitive ordering, and return the index of its root node.
+    fn build(
+        nodes: &mut Vec<IndexBVHNode>,
+        bboxes: &[AABB],
+        order: &mut [usize],
+        offset: usize,
+    ) -> usize {
+        let bbox = order[1..]
+            .iter()
+            .fold(bboxes[order[0]], |acc, &i| AABB::surrounding_box(acc, bboxes[i]));
+
+        let node_idx = nodes.len();
+        if order.len() <= MAX_LEAF_PRIMITIVES {
+            nodes.push(IndexBVHNode::Leaf { start: offset, count: order.len(), bbox });
+            return node_idx;
+        }
+
+        // Split at the median centroid along the axis with the widest spread of centroids
+        let centroid = |i: usize| 0.5 * (bboxes[i].min() + bboxes[i].max());
+        let (cmin, cmax) =
+            order.iter().fold((centroid(order[0]), centroid(order[0])), |(cmin, cmax), &i| {
+                (cmin.min(centroid(i)), cmax.max(centroid(i)))
+            });
+        let extent = cmax - cmin;
+        let axis = if extent.x > extent.y && extent.x > extent.z {
+            0
+        } else if extent.y > extent.z {
+            1
+        } else {
+            2
+        };
+
+        let mid = order.len() / 2;
+        order.select_nth_unstable_by(mid, |&a, &b| {
+            centroid(a)[axis].partial_cmp(&centroid(b)[axis]).unwrap_or(Ordering::Equal)
+        });
+
+        // Reserve the node slot before building the children
+        nodes.push(IndexBVHNode::Leaf { start: offset, count: 0, bbox });
+        let (left_order, right_order) = order.split_at_mut(mid);
+        let left = Self::build(nodes, bboxes, left_order, offset);
+        let right = Self::build(nodes, bboxes, right_order, offset + mid);
+        nodes[node_idx] = IndexBVHNode::Internal { left, right, bbox };
+
+        node_idx
+    }
+
+    pub fn bounding_box(&self) -> Option<AABB> {
+        self.nodes.first().map(|node| *node.bbox())
+    }
+
+    /// Find the closest primitive hit along the ray between `s_min` and `s_max`.
+    ///
+    /// `hit_primitive` is called with a primitive id and the current search interval,
+    /// and returns the contour position of the hit along with any data needed to
+    /// build the final hit record.
+    pub fn hit<T, F>(
+        &self,
+        r: &Ray,
+        s_min: f64,
+        s_max: f64,
+        mut hit_primitive: F,
+    ) -> Option<(f64, T)>
+    where
+        F: FnMut(usize, f64, f64) -> Option<(f64, T)>,
+    {
+        let mut closest: Option<(f64, T)> = None;
+        let mut closest_s = s_max;
+
+        let mut stack = Vec::with_capacity(64);
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(node_idx) = stack.pop() {
+            stats::increment(Counter::BVHNodeVisits);
+
+            let node = &self.nodes[node_idx];
+            if !node.bbox().hit(r, s_min, closest_s) {
+                continue;
+            }
+
+            match node {
+                IndexBVHNode::Leaf { start, count, .. } => {
+                    for &id in &self.ids[*start..*start + *count] {
+                        if let Some((s, data)) = hit_primitive(id, s_min, closest_s) {
+                            closest_s = s;
+                            closest = Some((s, data));
+                        }
+                    }
+                }
+                IndexBVHNode::Internal { left, right, .. } => {
+                    stack.push(*right);
+                    stack.push(*left);
+                }
+            }
+        }
+
+        closest
     }
 }
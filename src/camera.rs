@@ -8,6 +8,14 @@ use crate::{
     vec::{Vec3, VecOps},
 };
 
+/// Options describing the camera placement and the output image.
+///
+/// ```
+/// use raytracing_rust::camera::ViewOptions;
+///
+/// let opts = ViewOptions::new().with_apsect_ratio(2.0).with_image_width(400);
+/// assert_eq!(opts.image_height, 200);
+/// ```
 #[derive(Clone, Debug)]
 pub struct ViewOptions {
     pub background: Color,
@@ -22,6 +30,7 @@ pub struct ViewOptions {
     pub image_width: u64,
     pub image_height: u64,
     pub samples_per_pixel: u64,
+    pub samples_per_pass: u64,
     pub max_depth: u64,
 }
 
@@ -87,6 +96,11 @@ impl ViewOptions {
         self
     }
 
+    pub fn with_samples_per_pass(mut self, samples_per_pass: u64) -> Self {
+        self.samples_per_pass = samples_per_pass;
+        self
+    }
+
     pub fn with_max_depth(mut self, max_depth: u64) -> Self {
         self.max_depth = max_depth;
         self
@@ -108,11 +122,13 @@ impl Default for ViewOptions {
             image_width: 512,
             image_height: (512.0 * 9.0 / 16.0) as u64,
             samples_per_pixel: 200,
+            samples_per_pass: 10,
             max_depth: 50,
         }
     }
 }
 
+/// A thin-lens camera that generates rays through the viewport.
 #[derive(Clone, Debug)]
 pub struct Camera {
     origin: Vec3,
@@ -174,6 +190,8 @@ impl Camera {
         )
     }
 
+    /// Get a ray through the viewport at normalized coordinates `s` and `t`,
+    /// measured from the lower-left corner.
     pub fn get_ray(&self, s: f64, t: f64) -> Ray {
         let mut rng = rand::thread_rng();
 
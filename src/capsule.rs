@@ -0,0 +1,117 @@
+use std::f64::consts::PI;
+
+use crate::{
+    aabb::AABB,
+    cylinder::intersect_side,
+    disk::azimuth,
+    float,
+    hit::{Hit, HitRecord},
+    material::Material,
+    onb::ONB,
+    ray::Ray,
+    roots::solve_quadratic,
+    vec::Vec3,
+};
+
+/// A cylinder with hemispherical ends, running along an axis vector from the center of one end.
+///
+/// The texture coordinate `u` is the angle around the axis and `v` is the distance
+/// along the profile from one pole to the other, as a fraction of its total length.
+///
+/// ```
+/// use raytracing_rust::{capsule::Capsule, color::Color, hit::Hit, material::Lambertian, ray::Ray, vec::Vec3};
+///
+/// let capsule = Capsule::new(
+///     Vec3::new(0.0, 0.0, 0.0),
+///     Vec3::new(0.0, 2.0, 0.0),
+///     0.5,
+///     Lambertian::from(Color::new(0.5, 0.5, 0.5)),
+/// );
+///
+/// let r = Ray::new(Vec3::new(0.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0), 0.0);
+/// let rec = capsule.hit(&r, 0.001, f64::INFINITY).unwrap();
+/// assert!((rec.point.y - 2.5).abs() < 1e-9 && (rec.v - 1.0).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Capsule {
+    base: Vec3,
+    axis: Vec3,
+    onb: ONB,
+    radius: f64,
+    material: Material,
+}
+
+impl Capsule {
+    pub fn new(base: Vec3, axis: Vec3, radius: f64, material: impl Into<Material>) -> Self {
+        let onb = ONB::from_w(axis);
+        Self { base, axis, onb, radius, material: material.into() }
+    }
+
+    pub fn base(&self) -> Vec3 {
+        self.base
+    }
+
+    pub fn axis(&self) -> Vec3 {
+        self.axis
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+}
+
+impl Hit for Capsule {
+    fn hit(&self, r: &Ray, s_min: f64, mut s_max: f64) -> Option<HitRecord> {
+        let origin = self.onb.to_local(r.origin() - self.base);
+        let direction = self.onb.to_local(r.direction());
+        let height = self.axis.length();
+        let radius = self.radius;
+
+        // Profile length from pole to pole, and the arc length of a quarter circle
+        let length = height + PI * radius;
+        let quarter = 0.5 * PI * radius;
+
+        let mut closest = None;
+        if let Some((s, p, normal)) =
+            intersect_side(origin, direction, radius, height, s_min, s_max)
+        {
+            closest = Some((s, p, normal, azimuth(normal), (quarter + p.z) / length));
+            s_max = s;
+        }
+
+        // Each hemisphere only covers the part of its sphere beyond the end of the cylinder
+        for (z, sign) in [(0.0, -1.0), (height, 1.0)] {
+            let oc = origin - Vec3::new(0.0, 0.0, z);
+            let a = direction.length_squared();
+            let half_b = oc.dot(direction);
+            let c = oc.length_squared() - radius * radius;
+            if let Some((s0, s1)) = solve_quadratic(a, half_b, c) {
+                for s in [s0, s1] {
+                    let normal = (oc + s * direction).normalize();
+                    if s >= s_min && s <= s_max && sign * normal.z >= 0.0 {
+                        let arc = radius * normal.z.clamp(-1.0, 1.0).asin();
+                        let v = (quarter + z + arc) / length;
+                        let point = Vec3::new(0.0, 0.0, z) + radius * normal;
+                        closest = Some((s, point, normal, azimuth(normal), v));
+                        s_max = s;
+                        break;
+                    }
+                }
+            }
+        }
+
+        let (s, local, normal, u, v) = closest?;
+        let (point, error) = float::frame_point(&self.onb, self.base, local, 6);
+        let mut rec = HitRecord::new(s, u, v, point, self.material.clone());
+        rec.error = error;
+        rec.set_face_normal(r, self.onb.to_world(normal));
+
+        Some(rec)
+    }
+
+    fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<AABB> {
+        let r = Vec3::splat(self.radius);
+        let end = self.base + self.axis;
+        Some(AABB::new(self.base.min(end) - r, self.base.max(end) + r))
+    }
+}
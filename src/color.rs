@@ -1,36 +1,57 @@
-use crate::{hit::Hit, hittable::HittableList, material::Scatter, ray::Ray, vec::Vec3};
+use crate::{
+    hit::Hit,
+    hittable::HittableList,
+    material::Scatter,
+    ray::Ray,
+    stats::{self, Counter},
+    vec::Vec3,
+};
 
 /// Type alias for vector of RGB values.
 pub type Color = Vec3;
 
+/// Conversion of accumulated sample colors to output pixel values.
 pub trait ColorFormat {
     fn format_color(&self, num_samples: u64) -> String;
+
+    fn to_rgb8(&self, num_samples: u64) -> [u8; 3];
 }
 
 impl ColorFormat for Color {
     fn format_color(&self, num_samples: u64) -> String {
-        let ir = (256.0 * (self.x / (num_samples as f64)).sqrt().clamp(0.0, 0.999)) as u64;
-        let ig = (256.0 * (self.y / (num_samples as f64)).sqrt().clamp(0.0, 0.999)) as u64;
-        let ib = (256.0 * (self.z / (num_samples as f64)).sqrt().clamp(0.0, 0.999)) as u64;
-
+        let [ir, ig, ib] = self.to_rgb8(num_samples);
         format!("{} {} {}", ir, ig, ib)
     }
+
+    fn to_rgb8(&self, num_samples: u64) -> [u8; 3] {
+        // Average the accumulated samples and gamma-correct for gamma = 2.0
+        let ir = (256.0 * (self.x / (num_samples as f64)).sqrt().clamp(0.0, 0.999)) as u8;
+        let ig = (256.0 * (self.y / (num_samples as f64)).sqrt().clamp(0.0, 0.999)) as u8;
+        let ib = (256.0 * (self.z / (num_samples as f64)).sqrt().clamp(0.0, 0.999)) as u8;
+
+        [ir, ig, ib]
+    }
 }
 
+/// Get the color seen along a ray, following scattered rays up to `depth` bounces.
 pub fn ray_color(r: &Ray, world: &HittableList, background: Color, depth: u64) -> Color {
     if depth == 0 {
         // If we've exceeded the ray bounce limit, no more light is gathered
+        stats::increment(Counter::DepthTerminations);
         return Color::new(0.0, 0.0, 0.0);
     }
 
-    if let Some(rec) = world.hit(r, 0.001, f64::INFINITY) {
+    if let Some(rec) = world.hit(r, 0.0, f64::INFINITY) {
         let emitted = rec.material.emitted(rec.point, rec.u, rec.v);
         if let Some((attenuation, scattered)) = rec.material.scatter(r, &rec) {
+            stats::increment(Counter::SecondaryRays);
             emitted + attenuation * ray_color(&scattered, world, background, depth - 1)
         } else {
+            stats::increment(Counter::Absorptions);
             emitted
         }
     } else {
+        stats::increment(Counter::Escapes);
         background
     }
 }
@@ -0,0 +1,123 @@
+use crate::{
+    aabb::AABB,
+    disk::{azimuth, disk_bounding_box, intersect_disk},
+    float,
+    hit::{Hit, HitRecord},
+    material::Material,
+    onb::ONB,
+    ray::Ray,
+    roots::solve_quadratic,
+    vec::Vec3,
+};
+
+/// A circular cone with its base centered at a point and its apex at the end of an axis vector.
+///
+/// Cones are closed at the base unless built `with_cap(false)`.
+/// On the side, `u` is the angle around the axis and `v` the fraction of the height,
+/// while on the base `v` is the distance from the axis as a fraction of the radius.
+///
+/// ```
+/// use raytracing_rust::{color::Color, cone::Cone, hit::Hit, material::Lambertian, ray::Ray, vec::Vec3};
+///
+/// let cone = Cone::new(
+///     Vec3::new(0.0, 0.0, 0.0),
+///     Vec3::new(0.0, 1.0, 0.0),
+///     1.0,
+///     Lambertian::from(Color::new(0.5, 0.5, 0.5)),
+/// );
+///
+/// let r = Ray::new(Vec3::new(-5.0, 0.5, 0.0), Vec3::new(1.0, 0.0, 0.0), 0.0);
+/// let rec = cone.hit(&r, 0.001, f64::INFINITY).unwrap();
+/// assert!((rec.point.x + 0.5).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Cone {
+    base: Vec3,
+    axis: Vec3,
+    onb: ONB,
+    radius: f64,
+    capped: bool,
+    material: Material,
+}
+
+impl Cone {
+    pub fn new(base: Vec3, axis: Vec3, radius: f64, material: impl Into<Material>) -> Self {
+        let onb = ONB::from_w(axis);
+        Self { base, axis, onb, radius, capped: true, material: material.into() }
+    }
+
+    pub fn with_cap(mut self, capped: bool) -> Self {
+        self.capped = capped;
+        self
+    }
+
+    pub fn base(&self) -> Vec3 {
+        self.base
+    }
+
+    pub fn axis(&self) -> Vec3 {
+        self.axis
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+}
+
+impl Hit for Cone {
+    fn hit(&self, r: &Ray, s_min: f64, mut s_max: f64) -> Option<HitRecord> {
+        let origin = self.onb.to_local(r.origin() - self.base);
+        let direction = self.onb.to_local(r.direction());
+        let height = self.axis.length();
+
+        // The side is where x^2 + y^2 = (k (h - z))^2, with k the slope of the radius
+        let k = self.radius / height;
+        let k2 = k * k;
+        let oh = height - origin.z;
+
+        let mut closest = None;
+
+        let a =
+            direction.x * direction.x + direction.y * direction.y - k2 * direction.z * direction.z;
+        let half_b = origin.x * direction.x + origin.y * direction.y + k2 * oh * direction.z;
+        let c = origin.x * origin.x + origin.y * origin.y - k2 * oh * oh;
+        if let Some((s0, s1)) = solve_quadratic(a, half_b, c) {
+            for s in [s0, s1] {
+                // Both nappes of the double cone solve the quadratic, so check the height
+                let p = origin + s * direction;
+                if s >= s_min && s <= s_max && (0.0..=height).contains(&p.z) {
+                    // Take the radius from the height of the point, so it lies on the side
+                    let rho = k * (height - p.z);
+                    let radial = Vec3::new(p.x, p.y, 0.0).normalize_or_zero();
+                    let point = Vec3::new(rho * radial.x, rho * radial.y, p.z);
+                    let normal = Vec3::new(point.x, point.y, k * rho).normalize_or_zero();
+                    closest = Some((s, point, normal, azimuth(p), p.z / height));
+                    s_max = s;
+                    break;
+                }
+            }
+        }
+
+        if self.capped {
+            if let Some((s, p)) = intersect_disk(origin, direction, 0.0, self.radius, s_min, s_max)
+            {
+                let v = p.truncate().length() / self.radius;
+                closest = Some((s, p, -Vec3::Z, azimuth(p), v));
+            }
+        }
+
+        let (s, local, normal, u, v) = closest?;
+        let (point, error) = float::frame_point(&self.onb, self.base, local, 7);
+        let mut rec = HitRecord::new(s, u, v, point, self.material.clone());
+        rec.error = error;
+        rec.set_face_normal(r, self.onb.to_world(normal));
+
+        Some(rec)
+    }
+
+    fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<AABB> {
+        let base = disk_bounding_box(self.base, self.axis, self.radius);
+        let apex = self.base + self.axis;
+        Some(AABB::surrounding_box(base, AABB::new(apex, apex)))
+    }
+}
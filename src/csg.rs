@@ -0,0 +1,151 @@
+use crate::{
+    aabb::AABB,
+    hit::{Hit, HitRecord, Interval},
+    hittable::Hittable,
+    ray::Ray,
+};
+
+/// Boolean operation combining the two children of a `Csg` object.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CsgOperation {
+    /// Inside either child.
+    Union,
+    /// Inside both children.
+    Intersection,
+    /// Inside the first child but not the second.
+    Difference,
+}
+
+impl CsgOperation {
+    fn contains(self, in_left: bool, in_right: bool) -> bool {
+        match self {
+            Self::Union => in_left || in_right,
+            Self::Intersection => in_left && in_right,
+            Self::Difference => in_left && !in_right,
+        }
+    }
+}
+
+/// A solid built by combining two closed objects with a boolean operation.
+///
+/// The surface of the result is found from the spans of the ray inside each child,
+/// so both children must be closed with outward-facing normals. Surfaces cut out
+/// of the first child by a difference face into the removed region, which keeps
+/// `Dielectric` objects such as lenses refracting correctly.
+///
+/// ```
+/// use raytracing_rust::{
+///     color::Color, csg::Csg, hit::Hit, material::Lambertian, ray::Ray, sphere::Sphere,
+///     vec::Vec3,
+/// };
+///
+/// // A sphere with a bite taken out of its front
+/// let material = Lambertian::from(Color::new(0.5, 0.5, 0.5));
+/// let body = Sphere::stationary(Vec3::new(0.0, 0.0, 0.0), 1.0, material.clone());
+/// let bite = Sphere::stationary(Vec3::new(0.0, 0.0, 1.0), 0.5, material);
+/// let bitten = Csg::difference(body, bite);
+///
+/// let r = Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0), 0.0);
+/// let rec = bitten.hit(&r, 0.001, f64::INFINITY).unwrap();
+/// assert!((rec.point.z - 0.5).abs() < 1e-6);
+/// assert!(rec.front_face && rec.normal.z > 0.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Csg {
+    operation: CsgOperation,
+    left: Box<Hittable>,
+    right: Box<Hittable>,
+}
+
+impl Csg {
+    pub fn new(
+        operation: CsgOperation,
+        left: impl Into<Hittable>,
+        right: impl Into<Hittable>,
+    ) -> Self {
+        Self { operation, left: Box::new(left.into()), right: Box::new(right.into()) }
+    }
+
+    pub fn union(left: impl Into<Hittable>, right: impl Into<Hittable>) -> Self {
+        Self::new(CsgOperation::Union, left, right)
+    }
+
+    pub fn intersection(left: impl Into<Hittable>, right: impl Into<Hittable>) -> Self {
+        Self::new(CsgOperation::Intersection, left, right)
+    }
+
+    pub fn difference(left: impl Into<Hittable>, right: impl Into<Hittable>) -> Self {
+        Self::new(CsgOperation::Difference, left, right)
+    }
+
+    pub fn operation(&self) -> CsgOperation {
+        self.operation
+    }
+}
+
+/// Mark a boundary of the combined solid as entering or exiting it,
+/// which flips the normals of surfaces that bound it from the other side.
+fn orient(rec: Option<HitRecord>, entering: bool) -> Option<HitRecord> {
+    rec.map(|mut rec| {
+        rec.front_face = entering;
+        rec
+    })
+}
+
+impl Hit for Csg {
+    fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<HitRecord> {
+        self.intervals(r)
+            .into_iter()
+            .flat_map(|interval| [interval.enter, interval.exit])
+            .flatten()
+            .find(|rec| rec.s >= s_min && rec.s <= s_max)
+    }
+
+    fn bounding_box(&self, time0: f64, time1: f64) -> Option<AABB> {
+        let left = self.left.bounding_box(time0, time1);
+        let right = self.right.bounding_box(time0, time1);
+        match self.operation {
+            CsgOperation::Union => Some(AABB::surrounding_box(left?, right?)),
+            CsgOperation::Intersection => match (left, right) {
+                (Some(l), Some(r)) => Some(AABB::new(l.min().max(r.min()), l.max().min(r.max()))),
+                (bbox, None) | (None, bbox) => bbox,
+            },
+            CsgOperation::Difference => left,
+        }
+    }
+
+    fn intervals(&self, r: &Ray) -> Vec<Interval> {
+        // Sweep along the ray through the boundaries of both children in order,
+        // tracking whether the ray is inside each child and the combined solid
+        let mut events = Vec::new();
+        for (is_left, child) in [(true, &self.left), (false, &self.right)] {
+            for interval in child.intervals(r) {
+                let (start, end) = (interval.start(), interval.end());
+                events.push((start, is_left, true, interval.enter));
+                events.push((end, is_left, false, interval.exit));
+            }
+        }
+        events.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut intervals = Vec::new();
+        let (mut in_left, mut in_right) = (false, false);
+        let mut enter = None;
+        for (_, is_left, entering, rec) in events {
+            let was_inside = self.operation.contains(in_left, in_right);
+            if is_left {
+                in_left = entering;
+            } else {
+                in_right = entering;
+            }
+            let inside = self.operation.contains(in_left, in_right);
+
+            if inside && !was_inside {
+                enter = orient(rec, true);
+            } else if was_inside && !inside {
+                intervals.push(Interval { enter: enter.take(), exit: orient(rec, false) });
+            }
+        }
+
+        intervals
+    }
+}
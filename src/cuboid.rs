@@ -3,11 +3,33 @@ use crate::{
     hit::{Hit, HitRecord},
     hittable::{Hittable, HittableList},
     material::Material,
+    quad::Quad,
     ray::Ray,
     rectangle::{XYRectangle, XZRectangle, YZRectangle},
     vec::Vec3,
 };
 
+/// A box built from six rectangles or quads.
+///
+/// Boxes made with `new` are axis-aligned, while `oriented` boxes have arbitrary edges.
+/// The `min` and `max` corners are those of the box's bounding box.
+///
+/// ```
+/// use raytracing_rust::{color::Color, cuboid::Cuboid, hit::Hit, material::Lambertian, ray::Ray, vec::Vec3};
+///
+/// // A unit cube rotated by 45 degrees about the Z axis
+/// let diag = std::f64::consts::FRAC_1_SQRT_2;
+/// let cube = Cuboid::oriented(
+///     Vec3::new(0.0, 0.0, 0.0),
+///     [Vec3::new(diag, diag, 0.0), Vec3::new(-diag, diag, 0.0), Vec3::new(0.0, 0.0, 1.0)],
+///     Lambertian::from(Color::new(0.5, 0.5, 0.5)),
+/// );
+///
+/// let r = Ray::new(Vec3::new(0.1, 5.0, 0.5), Vec3::new(0.0, -1.0, 0.0), 0.0);
+/// let rec = cube.hit(&r, 0.001, f64::INFINITY).unwrap();
+/// assert!((rec.point.y - (2.0_f64.sqrt() - 0.1)).abs() < 1e-9);
+/// assert!(rec.normal.y > 0.0);
+/// ```
 #[derive(Clone, Debug)]
 pub struct Cuboid {
     min: Vec3,
@@ -29,6 +51,42 @@ impl Cuboid {
         Self { min, max, sides }
     }
 
+    /// Create a box from a corner and the three edge vectors leaving it,
+    /// which need not be aligned with the coordinate axes or with each other.
+    pub fn oriented(corner: Vec3, edges: [Vec3; 3], material: impl Into<Material>) -> Self {
+        let mat = material.into();
+        let [a, b, c] = edges;
+        let opposite = corner + a + b + c;
+
+        let mut sides = HittableList::new();
+        for (e1, e2, e3) in [(a, b, c), (b, c, a), (c, a, b)] {
+            sides.push(Self::face(corner, e1, e2, -e3, mat.clone()));
+            sides.push(Self::face(opposite, -e1, -e2, e3, mat.clone()));
+        }
+
+        let mut min = Vec3::splat(f64::INFINITY);
+        let mut max = Vec3::splat(f64::NEG_INFINITY);
+        for i in 0..8 {
+            let p = corner
+                + if i & 1 != 0 { a } else { Vec3::ZERO }
+                + if i & 2 != 0 { b } else { Vec3::ZERO }
+                + if i & 4 != 0 { c } else { Vec3::ZERO };
+            min = min.min(p);
+            max = max.max(p);
+        }
+
+        Self { min, max, sides }
+    }
+
+    /// Build the face spanned by two edges, ordered so its normal points along `outward`.
+    fn face(corner: Vec3, e1: Vec3, e2: Vec3, outward: Vec3, material: Material) -> Quad {
+        if e1.cross(e2).dot(outward) >= 0.0 {
+            Quad::new(corner, e1, e2, material)
+        } else {
+            Quad::new(corner, e2, e1, material)
+        }
+    }
+
     pub fn min(&self) -> Vec3 {
         self.min
     }
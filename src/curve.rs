@@ -0,0 +1,266 @@
+use std::sync::Arc;
+
+use crate::{
+    aabb::AABB,
+    bvh::IndexBVH,
+    disk::azimuth,
+    float,
+    hit::{Hit, HitRecord},
+    material::Material,
+    onb::ONB,
+    ray::Ray,
+    roots::solve_quadratic,
+    vec::Vec3,
+};
+
+/// A cubic Bézier curve whose width varies linearly from its start to its end.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Curve {
+    pub points: [Vec3; 4],
+    pub widths: (f64, f64),
+}
+
+impl Curve {
+    pub fn new(points: [Vec3; 4], start_width: f64, end_width: f64) -> Self {
+        Self { points, widths: (start_width, end_width) }
+    }
+
+    /// Evaluate the position on the curve at parameter `t` from 0 to 1.
+    pub fn point_at(&self, t: f64) -> Vec3 {
+        let [p0, p1, p2, p3] = self.points;
+        let s = 1.0 - t;
+        s * s * s * p0 + 3.0 * s * s * t * p1 + 3.0 * s * t * t * p2 + t * t * t * p3
+    }
+
+    pub fn width_at(&self, t: f64) -> f64 {
+        (1.0 - t) * self.widths.0 + t * self.widths.1
+    }
+}
+
+/// How the cross-section of a curve is rendered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveShape {
+    /// A flat strip that always faces the incoming ray, for thin strands such as hair.
+    Ribbon,
+    /// A round tube.
+    Tube,
+}
+
+/// A set of curves sharing a material, such as the strands of fur or blades of grass.
+///
+/// Each curve is approximated by a number of straight pieces, which are kept in the
+/// set's own BVH, so the whole set can be placed in a scene as one hittable object.
+/// The texture coordinate `u` runs along each curve from 0 to 1, and `v` runs across
+/// a ribbon from one edge to the other or around a tube.
+///
+/// ```
+/// use raytracing_rust::{
+///     color::Color,
+///     curve::{Curve, CurveShape, Curves},
+///     hit::Hit,
+///     material::Lambertian,
+///     ray::Ray,
+///     vec::Vec3,
+/// };
+///
+/// // A straight strand along the X axis, tapering from a width of 0.2 to zero
+/// let points = [0.0, 1.0, 2.0, 3.0].map(|x| Vec3::new(x, 0.0, 0.0));
+/// let curves = vec![Curve::new(points, 0.2, 0.0)];
+/// let material = Lambertian::from(Color::new(0.5, 0.5, 0.5));
+/// let hair = Curves::new(curves, 8, CurveShape::Tube, material);
+///
+/// let r = Ray::new(Vec3::new(1.5, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0), 0.0);
+/// let rec = hair.hit(&r, 0.001, f64::INFINITY).unwrap();
+/// assert!((rec.point.z - 0.05).abs() < 1e-9 && (rec.u - 0.5).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Curves {
+    curves: Arc<Vec<Curve>>,
+    subdivisions: usize,
+    bvh: Arc<IndexBVH>,
+    shape: CurveShape,
+    material: Material,
+}
+
+/// A straight piece of a curve, between parameters `t0` and `t1`.
+struct Piece {
+    p0: Vec3,
+    p1: Vec3,
+    r0: f64,
+    r1: f64,
+    t0: f64,
+    t1: f64,
+}
+
+/// A hit on a piece, with the point moved onto the surface and its error bound.
+struct PieceHit {
+    s: f64,
+    point: Vec3,
+    error: Vec3,
+    normal: Vec3,
+    u: f64,
+    v: f64,
+}
+
+impl Curves {
+    /// Create a set of curves, each split into `subdivisions` straight pieces.
+    pub fn new(
+        curves: Vec<Curve>,
+        subdivisions: usize,
+        shape: CurveShape,
+        material: impl Into<Material>,
+    ) -> Self {
+        let subdivisions = subdivisions.max(1);
+        let curves = Arc::new(curves);
+
+        let n = curves.len() * subdivisions;
+        let bvh = IndexBVH::new((0..n).collect(), |id| {
+            let piece = Self::piece(&curves, subdivisions, id);
+            let r = Vec3::splat(piece.r0.max(piece.r1));
+            AABB::new(piece.p0.min(piece.p1) - r, piece.p0.max(piece.p1) + r)
+        });
+
+        Self { curves, subdivisions, bvh: Arc::new(bvh), shape, material: material.into() }
+    }
+
+    pub fn curves(&self) -> &Arc<Vec<Curve>> {
+        &self.curves
+    }
+
+    fn piece(curves: &[Curve], subdivisions: usize, id: usize) -> Piece {
+        let curve = &curves[id / subdivisions];
+        let k = id % subdivisions;
+        let t0 = k as f64 / subdivisions as f64;
+        let t1 = (k + 1) as f64 / subdivisions as f64;
+        Piece {
+            p0: curve.point_at(t0),
+            p1: curve.point_at(t1),
+            r0: 0.5 * curve.width_at(t0),
+            r1: 0.5 * curve.width_at(t1),
+            t0,
+            t1,
+        }
+    }
+
+    /// Intersect a piece as a ribbon.
+    ///
+    /// A ribbon turns to face each ray, so it sweeps through the whole round reach of
+    /// its piece. A ray starting within that reach must have left this piece or the one
+    /// joining it, and skips it rather than hitting a slightly turned copy of the same ribbon.
+    fn hit_ribbon(r: &Ray, piece: &Piece, s_min: f64, s_max: f64) -> Option<PieceHit> {
+        // Find the closest approach between the ray and the axis of the piece
+        let axis = piece.p1 - piece.p0;
+        let w0 = r.origin() - piece.p0;
+        let (a, b, c) =
+            (r.direction().length_squared(), r.direction().dot(axis), axis.length_squared());
+        let (d, e) = (r.direction().dot(w0), axis.dot(w0));
+        let denom = a * c - b * b;
+        if denom.abs() <= 1e-12 * a * c || Self::within_reach(r.origin(), piece) {
+            return None;
+        }
+
+        let s = (b * e - c * d) / denom;
+        let w = (a * e - b * d) / denom;
+        if s < s_min || s > s_max || !(0.0..=1.0).contains(&w) {
+            return None;
+        }
+
+        let radius = (1.0 - w) * piece.r0 + w * piece.r1;
+        let center = piece.p0 + w * axis;
+        let offset = r.at(s) - center;
+        if offset.length_squared() > radius * radius {
+            return None;
+        }
+
+        // The ribbon faces back along the ray, turned about the axis of the piece
+        let axis_dir = axis / c.sqrt();
+        let facing = r.direction() - r.direction().dot(axis_dir) * axis_dir;
+        let normal = -facing.normalize();
+        let (point, error) = float::plane_point(center, normal, r.at(s));
+
+        let side = axis_dir.cross(normal);
+        let v = 0.5 + 0.5 * offset.dot(side) / radius;
+        let u = piece.t0 + w * (piece.t1 - piece.t0);
+
+        Some(PieceHit { s, point, error, normal, u, v })
+    }
+
+    /// Determine if a point is within the round reach of a piece, allowing for the offset
+    /// of a ray spawned from a ribbon hit past its error bounds.
+    fn within_reach(point: Vec3, piece: &Piece) -> bool {
+        let axis = piece.p1 - piece.p0;
+        let w = ((point - piece.p0).dot(axis) / axis.length_squared()).clamp(0.0, 1.0);
+        let radius = (1.0 - w) * piece.r0 + w * piece.r1;
+        let slack =
+            2.0 * float::gamma(7) * (point.abs() + piece.p0.abs() + axis.abs()).max_element();
+        point.distance(piece.p0 + w * axis) <= radius + slack
+    }
+
+    /// Intersect a piece as a tube, whose radius tapers linearly along the piece like a cone.
+    fn hit_tube(r: &Ray, piece: &Piece, s_min: f64, s_max: f64) -> Option<PieceHit> {
+        let axis = piece.p1 - piece.p0;
+        let length = axis.length();
+        if length == 0.0 {
+            return None;
+        }
+        let onb = ONB::from_w(axis);
+
+        // Work in the frame of the piece, with its axis along Z from the origin,
+        // where the surface is x^2 + y^2 = (r0 + k z)^2
+        let origin = onb.to_local(r.origin() - piece.p0);
+        let direction = onb.to_local(r.direction());
+        let k = (piece.r1 - piece.r0) / length;
+        let radius_at_origin = piece.r0 + k * origin.z;
+
+        let a = direction.x * direction.x + direction.y * direction.y
+            - k * k * direction.z * direction.z;
+        let half_b =
+            origin.x * direction.x + origin.y * direction.y - k * radius_at_origin * direction.z;
+        let c = origin.x * origin.x + origin.y * origin.y - radius_at_origin * radius_at_origin;
+        let (s0, s1) = solve_quadratic(a, half_b, c)?;
+
+        for s in [s0, s1] {
+            let p = origin + s * direction;
+            let rho = p.x.hypot(p.y);
+            if s < s_min || s > s_max || !(0.0..=length).contains(&p.z) || rho == 0.0 {
+                continue;
+            }
+
+            // Move the point radially onto the surface, which removes the error of
+            // the contour position for rays grazing the tube
+            let radius = piece.r0 + k * p.z;
+            let local = Vec3::new(p.x * radius / rho, p.y * radius / rho, p.z);
+            let (point, error) = float::frame_point(&onb, piece.p0, local, 7);
+
+            let normal = Vec3::new(p.x / rho, p.y / rho, -k).normalize();
+            let u = piece.t0 + (p.z / length) * (piece.t1 - piece.t0);
+            let v = azimuth(normal);
+            return Some(PieceHit { s, point, error, normal: onb.to_world(normal), u, v });
+        }
+
+        None
+    }
+}
+
+impl Hit for Curves {
+    fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<HitRecord> {
+        let (_, hit) = self.bvh.hit(r, s_min, s_max, |id, s_min, s_max| {
+            let piece = Self::piece(&self.curves, self.subdivisions, id);
+            let hit = match self.shape {
+                CurveShape::Ribbon => Self::hit_ribbon(r, &piece, s_min, s_max),
+                CurveShape::Tube => Self::hit_tube(r, &piece, s_min, s_max),
+            };
+            hit.map(|hit| (hit.s, hit))
+        })?;
+
+        let mut rec = HitRecord::new(hit.s, hit.u, hit.v, hit.point, self.material.clone());
+        rec.error = hit.error;
+        rec.set_face_normal(r, hit.normal);
+
+        Some(rec)
+    }
+
+    fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<AABB> {
+        self.bvh.bounding_box()
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{
+    aabb::AABB,
+    disk::{azimuth, disk_bounding_box, intersect_disk},
+    float,
+    hit::{Hit, HitRecord},
+    material::Material,
+    onb::ONB,
+    ray::Ray,
+    roots::solve_quadratic,
+    vec::Vec3,
+};
+
+/// A circular cylinder running along an axis vector from the center of its base.
+///
+/// Cylinders are capped at both ends unless built `with_caps(false)`.
+/// On the side, `u` is the angle around the axis and `v` the fraction of the height,
+/// while on the caps `v` is the distance from the axis as a fraction of the radius.
+///
+/// ```
+/// use raytracing_rust::{color::Color, cylinder::Cylinder, hit::Hit, material::Lambertian, ray::Ray, vec::Vec3};
+///
+/// let cylinder = Cylinder::new(
+///     Vec3::new(0.0, 0.0, 0.0),
+///     Vec3::new(0.0, 2.0, 0.0),
+///     1.0,
+///     Lambertian::from(Color::new(0.5, 0.5, 0.5)),
+/// );
+///
+/// let r = Ray::new(Vec3::new(-5.0, 1.5, 0.0), Vec3::new(1.0, 0.0, 0.0), 0.0);
+/// let rec = cylinder.hit(&r, 0.001, f64::INFINITY).unwrap();
+/// assert!((rec.point.x + 1.0).abs() < 1e-9 && (rec.v - 0.75).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Cylinder {
+    base: Vec3,
+    axis: Vec3,
+    onb: ONB,
+    radius: f64,
+    capped: bool,
+    material: Material,
+}
+
+impl Cylinder {
+    pub fn new(base: Vec3, axis: Vec3, radius: f64, material: impl Into<Material>) -> Self {
+        let onb = ONB::from_w(axis);
+        Self { base, axis, onb, radius, capped: true, material: material.into() }
+    }
+
+    pub fn with_caps(mut self, capped: bool) -> Self {
+        self.capped = capped;
+        self
+    }
+
+    pub fn base(&self) -> Vec3 {
+        self.base
+    }
+
+    pub fn axis(&self) -> Vec3 {
+        self.axis
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+}
+
+/// Intersect a ray, given in the local frame of a cylinder with its axis along Z from
+/// the origin, with the side of the cylinder between `z = 0` and `z = height`.
+///
+/// Returns the contour position of the nearest hit, the local hit point and the local
+/// outward normal. The point is moved radially onto the side, whatever the error of `s`.
+pub(crate) fn intersect_side(
+    origin: Vec3,
+    direction: Vec3,
+    radius: f64,
+    height: f64,
+    s_min: f64,
+    s_max: f64,
+) -> Option<(f64, Vec3, Vec3)> {
+    let a = direction.x * direction.x + direction.y * direction.y;
+    let half_b = origin.x * direction.x + origin.y * direction.y;
+    let c = origin.x * origin.x + origin.y * origin.y - radius * radius;
+    let (s0, s1) = solve_quadratic(a, half_b, c)?;
+
+    [s0, s1].into_iter().find_map(|s| {
+        let p = origin + s * direction;
+        if s < s_min || s > s_max || !(0.0..=height).contains(&p.z) {
+            return None;
+        }
+        let normal = Vec3::new(p.x, p.y, 0.0).normalize();
+        Some((s, Vec3::new(radius * normal.x, radius * normal.y, p.z), normal))
+    })
+}
+
+impl Hit for Cylinder {
+    fn hit(&self, r: &Ray, s_min: f64, mut s_max: f64) -> Option<HitRecord> {
+        let origin = self.onb.to_local(r.origin() - self.base);
+        let direction = self.onb.to_local(r.direction());
+        let height = self.axis.length();
+
+        // Closest hit so far as the local hit point, local outward normal and texture coordinates
+        let mut closest = None;
+
+        if let Some((s, p, normal)) =
+            intersect_side(origin, direction, self.radius, height, s_min, s_max)
+        {
+            closest = Some((s, p, normal, azimuth(normal), p.z / height));
+            s_max = s;
+        }
+
+        if self.capped {
+            for (z, normal) in [(0.0, -Vec3::Z), (height, Vec3::Z)] {
+                if let Some((s, p)) =
+                    intersect_disk(origin, direction, z, self.radius, s_min, s_max)
+                {
+                    let v = p.truncate().length() / self.radius;
+                    closest = Some((s, p, normal, azimuth(p), v));
+                    s_max = s;
+                }
+            }
+        }
+
+        let (s, local, normal, u, v) = closest?;
+        let (point, error) = float::frame_point(&self.onb, self.base, local, 5);
+        let mut rec = HitRecord::new(s, u, v, point, self.material.clone());
+        rec.error = error;
+        rec.set_face_normal(r, self.onb.to_world(normal));
+
+        Some(rec)
+    }
+
+    fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<AABB> {
+        let bottom = disk_bounding_box(self.base, self.axis, self.radius);
+        let top = disk_bounding_box(self.base + self.axis, self.axis, self.radius);
+        Some(AABB::surrounding_box(bottom, top))
+    }
+}
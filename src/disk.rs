@@ -0,0 +1,125 @@
+use std::f64::consts::PI;
+
+use crate::{
+    aabb::AABB,
+    float,
+    hit::{Hit, HitRecord},
+    material::Material,
+    onb::ONB,
+    ray::Ray,
+    triangle::padded_bounding_box,
+    vec::Vec3,
+};
+
+/// A flat circular disk facing along its normal.
+///
+/// The texture coordinate `u` is the angle around the normal and `v` is the distance
+/// from the center as a fraction of the radius.
+///
+/// ```
+/// use raytracing_rust::{color::Color, disk::Disk, hit::Hit, material::Lambertian, ray::Ray, vec::Vec3};
+///
+/// let disk = Disk::new(
+///     Vec3::new(0.0, 0.0, 0.0),
+///     Vec3::new(0.0, 1.0, 0.0),
+///     2.0,
+///     Lambertian::from(Color::new(0.5, 0.5, 0.5)),
+/// );
+///
+/// let r = Ray::new(Vec3::new(1.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0), 0.0);
+/// let rec = disk.hit(&r, 0.001, f64::INFINITY).unwrap();
+/// assert!((rec.v - 0.5).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Disk {
+    center: Vec3,
+    onb: ONB,
+    radius: f64,
+    material: Material,
+}
+
+impl Disk {
+    pub fn new(center: Vec3, normal: Vec3, radius: f64, material: impl Into<Material>) -> Self {
+        Self { center, onb: ONB::from_w(normal), radius, material: material.into() }
+    }
+
+    pub fn center(&self) -> Vec3 {
+        self.center
+    }
+
+    pub fn normal(&self) -> Vec3 {
+        self.onb.w()
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+}
+
+/// Get the angle around the local `w` axis of a point in an object's frame, scaled to `[0, 1]`.
+pub(crate) fn azimuth(local: Vec3) -> f64 {
+    (local.y.atan2(local.x) + PI) / (2.0 * PI)
+}
+
+/// Intersect a ray, given in the local frame of a disk in the plane `z = height`,
+/// with that disk. Returns the contour position and the local hit point.
+pub(crate) fn intersect_disk(
+    origin: Vec3,
+    direction: Vec3,
+    height: f64,
+    radius: f64,
+    s_min: f64,
+    s_max: f64,
+) -> Option<(f64, Vec3)> {
+    if direction.z == 0.0 {
+        return None;
+    }
+
+    let s = (height - origin.z) / direction.z;
+    if s < s_min || s > s_max {
+        return None;
+    }
+
+    // The hit point lies exactly in the plane of the disk, whatever the error of `s`
+    let mut p = origin + s * direction;
+    p.z = height;
+    if p.x * p.x + p.y * p.y > radius * radius {
+        return None;
+    }
+
+    Some((s, p))
+}
+
+/// Get the bounding box of a disk, padded along any axis it is perpendicular to.
+pub(crate) fn disk_bounding_box(center: Vec3, normal: Vec3, radius: f64) -> AABB {
+    let n = normal.normalize();
+    let extent = radius
+        * Vec3::new(
+            (1.0 - n.x * n.x).max(0.0).sqrt(),
+            (1.0 - n.y * n.y).max(0.0).sqrt(),
+            (1.0 - n.z * n.z).max(0.0).sqrt(),
+        );
+    padded_bounding_box(&[center - extent, center + extent])
+}
+
+impl Hit for Disk {
+    fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<HitRecord> {
+        let origin = self.onb.to_local(r.origin() - self.center);
+        let direction = self.onb.to_local(r.direction());
+        let (s, local) = intersect_disk(origin, direction, 0.0, self.radius, s_min, s_max)?;
+
+        let u = azimuth(local);
+        let v = local.truncate().length() / self.radius;
+
+        let (point, error) = float::frame_point(&self.onb, self.center, local, 3);
+        let mut rec = HitRecord::new(s, u, v, point, self.material.clone());
+        rec.error = error;
+        rec.set_face_normal(r, self.onb.w());
+
+        Some(rec)
+    }
+
+    fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<AABB> {
+        Some(disk_bounding_box(self.center, self.onb.w(), self.radius))
+    }
+}
@@ -0,0 +1,335 @@
+use std::{
+    collections::HashMap,
+    f64::consts::{PI, SQRT_2},
+};
+
+use crate::{
+    mesh::{MeshData, MeshTriangle},
+    quad::Quad,
+    sphere::Sphere,
+    texture::{Texture, TextureColor},
+    vec::Vec3,
+};
+
+/// Geometric displacement of a surface by a scalar texture.
+///
+/// The base surface is tessellated until no edge is longer than the maximum edge length,
+/// each vertex is pushed along its normal by the texture's brightness times the scale,
+/// and the normals of the displaced mesh are recomputed from its faces.
+/// The result is mesh data, to be rendered as a `TriangleMesh` with any material.
+///
+/// Textures are looked up at the undisplaced vertex position and texture coordinates,
+/// so noise textures work in space and image textures follow the surface's UVs.
+///
+/// ```
+/// use raytracing_rust::{
+///     color::Color, displace::Displacement, material::Lambertian, mesh::TriangleMesh,
+///     quad::Quad, texture::SolidColor, vec::Vec3,
+/// };
+///
+/// // A flat 2 by 2 quad raised uniformly by half the scale of a mid-grey texture
+/// let material = Lambertian::from(Color::new(0.5, 0.5, 0.5));
+/// let quad = Quad::new(Vec3::ZERO, Vec3::X * 2.0, Vec3::Z * -2.0, material.clone());
+/// let bump = Displacement::new(SolidColor::new(Color::splat(0.5)), 0.4, 0.3);
+///
+/// // Cells of 0.2 by 0.2 keep the diagonals within the maximum edge length
+/// let data = bump.apply_to_quad(&quad);
+/// assert_eq!(data.triangles.len(), 2 * 10 * 10);
+/// assert!(data.positions.iter().all(|p| (p.y - 0.2).abs() < 1e-9));
+/// let terrain = TriangleMesh::new(data, material);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Displacement {
+    texture: Texture,
+    scale: f64,
+    max_edge_length: f64,
+}
+
+/// A vertex of a surface being tessellated, with its own normal and texture coordinates.
+#[derive(Clone, Copy, Debug)]
+struct Vertex {
+    position: Vec3,
+    normal: Vec3,
+    uv: (f64, f64),
+}
+
+impl Vertex {
+    fn midpoint(a: &Self, b: &Self) -> Self {
+        Self {
+            position: 0.5 * (a.position + b.position),
+            normal: (a.normal + b.normal).normalize_or_zero(),
+            uv: (0.5 * (a.uv.0 + b.uv.0), 0.5 * (a.uv.1 + b.uv.1)),
+        }
+    }
+}
+
+impl Displacement {
+    pub fn new(texture: impl Into<Texture>, scale: f64, max_edge_length: f64) -> Self {
+        assert!(max_edge_length > 0.0, "The maximum edge length must be positive.");
+        Self { texture: texture.into(), scale, max_edge_length }
+    }
+
+    /// Displace a quad, tessellated as a grid with `u` and `v` along its two edges.
+    pub fn apply_to_quad(&self, quad: &Quad) -> MeshData {
+        let [edge1, edge2] = quad.edges();
+        let nu = self.segments(SQRT_2 * edge1.length());
+        let nv = self.segments(SQRT_2 * edge2.length());
+
+        let grid = (0..=nv).flat_map(|j| (0..=nu).map(move |i| (i, j)));
+        let vertices = grid
+            .map(|(i, j)| {
+                let uv = (i as f64 / nu as f64, j as f64 / nv as f64);
+                let position = quad.corner() + uv.0 * edge1 + uv.1 * edge2;
+                Vertex { position, normal: quad.normal(), uv }
+            })
+            .collect();
+
+        let triangles = grid_triangles(nu, nv);
+        self.displace(vertices, triangles)
+    }
+
+    /// Displace a sphere at its position at time zero, tessellated by latitude and longitude
+    /// with the same texture coordinates as the sphere itself.
+    ///
+    /// The vertices along the seam and at the poles are moved together, so the mesh stays closed
+    /// even where the texture differs between them.
+    ///
+    /// ```
+    /// use raytracing_rust::{
+    ///     color::Color, displace::Displacement, material::Lambertian, sphere::Sphere,
+    ///     texture::ImageTexture, vec::Vec3,
+    /// };
+    ///
+    /// let material = Lambertian::from(Color::new(0.5, 0.5, 0.5));
+    /// let sphere = Sphere::stationary(Vec3::ZERO, 1.0, material);
+    /// let earth = concat!(env!("CARGO_MANIFEST_DIR"), "/images/texture_earth_clouds.jpg");
+    /// let data = Displacement::new(ImageTexture::new(earth), 0.2, 0.5).apply_to_sphere(&sphere);
+    ///
+    /// let position_at = |uv: (f64, f64)| data.uvs.iter().position(|&other| other == uv);
+    /// for (idx, &(u, v)) in data.uvs.iter().enumerate() {
+    ///     // Each vertex at the start of the seam meets its copy at the end
+    ///     if u == 0.0 {
+    ///         assert_eq!(data.positions[idx], data.positions[position_at((1.0, v)).unwrap()]);
+    ///     }
+    ///     // All vertices at a pole meet at one point
+    ///     if v == 0.0 || v == 1.0 {
+    ///         assert_eq!(data.positions[idx], data.positions[position_at((0.0, v)).unwrap()]);
+    ///     }
+    /// }
+    /// ```
+    pub fn apply_to_sphere(&self, sphere: &Sphere) -> MeshData {
+        let (center, radius) = (sphere.center(0.0), sphere.radius());
+        let nu = self.segments(SQRT_2 * 2.0 * PI * radius).max(3);
+        let nv = self.segments(SQRT_2 * PI * radius).max(2);
+
+        let grid = (0..=nv).flat_map(|j| (0..=nu).map(move |i| (i, j)));
+        let vertices = grid
+            .map(|(i, j)| {
+                let uv = (i as f64 / nu as f64, j as f64 / nv as f64);
+
+                // Wrap the seam and close the poles exactly, so the vertices there coincide
+                let theta = PI * uv.1;
+                let phi = 2.0 * PI * (i % nu) as f64 / nu as f64;
+                let sin_theta = if j == 0 || j == nv { 0.0 } else { theta.sin() };
+                let normal = Vec3::new(-sin_theta * phi.cos(), -theta.cos(), sin_theta * phi.sin());
+                Vertex { position: center + radius * normal, normal, uv }
+            })
+            .collect();
+
+        // Each grid triangle has one edge along a row, which collapses to a point at the poles
+        let row = |i: u32| i as usize / (nu + 1);
+        let triangles = grid_triangles(nu, nv)
+            .into_iter()
+            .filter(|&[a, b, c]| {
+                let edge_row = if row(a) == row(b) { row(a) } else { row(c) };
+                edge_row != 0 && edge_row != nv
+            })
+            .collect();
+        self.displace(vertices, triangles)
+    }
+
+    /// Displace the static positions of a mesh, using its vertex normals where it has them
+    /// and smooth normals averaged from its faces elsewhere.
+    pub fn apply_to_mesh(&self, data: &MeshData) -> MeshData {
+        let face_normals = smooth_normals(
+            &data.positions,
+            &data.triangles.iter().map(|t| t.positions).collect::<Vec<_>>(),
+        );
+
+        // Give every distinct combination of attributes used by a corner its own vertex
+        let mut vertices = Vec::new();
+        let mut ids = HashMap::new();
+        let triangles = data
+            .triangles
+            .iter()
+            .map(|triangle| {
+                [0, 1, 2].map(|k| {
+                    let key = (
+                        triangle.positions[k],
+                        triangle.normals.map(|n| n[k]),
+                        triangle.uvs.map(|uv| uv[k]),
+                    );
+                    *ids.entry(key).or_insert_with(|| {
+                        let (p, n, uv) = key;
+                        vertices.push(Vertex {
+                            position: data.positions[p as usize],
+                            normal: match n {
+                                Some(n) => data.normals[n as usize].normalize(),
+                                None => face_normals[p as usize],
+                            },
+                            uv: uv.map_or((0.0, 0.0), |uv| data.uvs[uv as usize]),
+                        });
+                        vertices.len() as u32 - 1
+                    })
+                })
+            })
+            .collect();
+
+        self.displace(vertices, triangles)
+    }
+
+    /// Get the number of segments needed to split a length into edges no longer than the maximum.
+    ///
+    /// Grids pass their edge lengths scaled by the square root of two,
+    /// so the diagonals of square cells are short enough too.
+    fn segments(&self, length: f64) -> usize {
+        ((length / self.max_edge_length).ceil() as usize).max(1)
+    }
+
+    /// Split the long edges of a tessellation, move its vertices and build the displaced mesh.
+    ///
+    /// Vertices sharing a position, such as those along a seam in the texture coordinates
+    /// or a hard edge, are moved together by their average height along their average normal,
+    /// so the displaced surface does not tear apart between them.
+    fn displace(&self, vertices: Vec<Vertex>, triangles: Vec<[u32; 3]>) -> MeshData {
+        let (vertices, triangles) = self.split_long_edges(vertices, triangles);
+
+        let mut sums: HashMap<[u64; 3], (f64, Vec3, usize)> = HashMap::new();
+        for vertex in &vertices {
+            let color = self.texture.color_value(vertex.position, vertex.uv.0, vertex.uv.1);
+            let sum = sums.entry(position_key(vertex.position)).or_insert((0.0, Vec3::ZERO, 0));
+            sum.0 += (color.x + color.y + color.z) / 3.0;
+            sum.1 += vertex.normal;
+            sum.2 += 1;
+        }
+
+        let offsets: HashMap<[u64; 3], Vec3> = sums
+            .into_iter()
+            .map(|(key, (height, normal, count))| {
+                (key, self.scale * height / count as f64 * normal.normalize_or_zero())
+            })
+            .collect();
+        let positions: Vec<Vec3> = vertices
+            .iter()
+            .map(|vertex| vertex.position + offsets[&position_key(vertex.position)])
+            .collect();
+
+        let normals = smooth_normals(&positions, &triangles);
+        let uvs = vertices.iter().map(|vertex| vertex.uv).collect();
+        let triangles = triangles
+            .into_iter()
+            .map(|t| MeshTriangle { positions: t, normals: Some(t), uvs: Some(t) })
+            .collect();
+
+        MeshData { positions, normals, uvs, triangles, ..MeshData::new() }
+    }
+
+    /// Repeatedly split every edge longer than the maximum at its midpoint.
+    ///
+    /// The decision to split depends only on the edge, so both triangles sharing
+    /// an edge split it at the same new vertex and no cracks open between them.
+    fn split_long_edges(
+        &self,
+        mut vertices: Vec<Vertex>,
+        mut triangles: Vec<[u32; 3]>,
+    ) -> (Vec<Vertex>, Vec<[u32; 3]>) {
+        loop {
+            let mut midpoints: HashMap<(u32, u32), u32> = HashMap::new();
+            let mut split = Vec::with_capacity(triangles.len());
+
+            for [a, b, c] in triangles.iter().copied() {
+                let mut midpoint = |i: u32, j: u32| {
+                    let (vi, vj) = (vertices[i as usize], vertices[j as usize]);
+                    if vi.position.distance(vj.position) <= self.max_edge_length {
+                        return None;
+                    }
+                    let key = (i.min(j), i.max(j));
+                    Some(*midpoints.entry(key).or_insert_with(|| {
+                        vertices.push(Vertex::midpoint(&vi, &vj));
+                        vertices.len() as u32 - 1
+                    }))
+                };
+
+                let (ab, bc, ca) = (midpoint(a, b), midpoint(b, c), midpoint(c, a));
+                split.extend(split_triangle([a, b, c], [ab, bc, ca]));
+            }
+
+            let done = midpoints.is_empty();
+            triangles = split;
+            if done {
+                return (vertices, triangles);
+            }
+        }
+    }
+}
+
+/// Split triangle `(a, b, c)` at the midpoints of its edges `ab`, `bc` and `ca` that have them,
+/// keeping the winding order.
+fn split_triangle(tri: [u32; 3], mids: [Option<u32>; 3]) -> Vec<[u32; 3]> {
+    // Rotate the triangle so the edges to split come first
+    let count = mids.iter().flatten().count();
+    let rotation = (0..3)
+        .find(|&k| match count {
+            1 => mids[k].is_some(),
+            2 => mids[(k + 2) % 3].is_none(),
+            _ => true,
+        })
+        .unwrap_or(0);
+    let [a, b, c] = [0, 1, 2].map(|i| tri[(i + rotation) % 3]);
+    let [ab, bc, ca] = [0, 1, 2].map(|i| mids[(i + rotation) % 3]);
+
+    match (ab, bc, ca) {
+        (Some(ab), Some(bc), Some(ca)) => vec![[a, ab, ca], [ab, b, bc], [ca, bc, c], [ab, bc, ca]],
+        (Some(ab), Some(bc), None) => vec![[ab, b, bc], [a, ab, bc], [a, bc, c]],
+        (Some(ab), None, None) => vec![[a, ab, c], [ab, b, c]],
+        _ => vec![[a, b, c]],
+    }
+}
+
+/// Get the two triangles of each cell of a grid of `nu` by `nv` cells, with vertices in rows.
+fn grid_triangles(nu: usize, nv: usize) -> Vec<[u32; 3]> {
+    let idx = |i: usize, j: usize| (j * (nu + 1) + i) as u32;
+    (0..nv)
+        .flat_map(|j| (0..nu).map(move |i| (i, j)))
+        .flat_map(|(i, j)| {
+            [
+                [idx(i, j), idx(i + 1, j), idx(i + 1, j + 1)],
+                [idx(i, j), idx(i + 1, j + 1), idx(i, j + 1)],
+            ]
+        })
+        .collect()
+}
+
+/// Get a key identifying a position exactly, for finding the vertices that share it.
+fn position_key(p: Vec3) -> [u64; 3] {
+    [p.x.to_bits(), p.y.to_bits(), p.z.to_bits()]
+}
+
+/// Compute a normal for each position by summing the area-weighted normals of its faces,
+/// shared between positions that coincide so seams in the texture coordinates stay smooth.
+fn smooth_normals(positions: &[Vec3], triangles: &[[u32; 3]]) -> Vec<Vec3> {
+    let mut sums: HashMap<[u64; 3], Vec3> = HashMap::new();
+
+    for tri in triangles {
+        let [p0, p1, p2] = tri.map(|i| positions[i as usize]);
+        let face = (p1 - p0).cross(p2 - p0);
+        for p in [p0, p1, p2] {
+            *sums.entry(position_key(p)).or_insert(Vec3::ZERO) += face;
+        }
+    }
+
+    positions
+        .iter()
+        .map(|p| sums.get(&position_key(*p)).map_or(Vec3::ZERO, |n| n.normalize_or_zero()))
+        .collect()
+}
@@ -0,0 +1,58 @@
+use crate::{
+    onb::ONB,
+    ray::Ray,
+    vec::{Affine3, Mat3, Vec3},
+};
+
+/// Bound on the relative rounding error accumulated over `n` floating-point operations.
+///
+/// ```
+/// use raytracing_rust::float::gamma;
+///
+/// assert!(gamma(3) > 3.0 * f64::EPSILON / 2.0 && gamma(3) < 2.0 * f64::EPSILON);
+/// ```
+pub fn gamma(n: u32) -> f64 {
+    let e = n as f64 * 0.5 * f64::EPSILON;
+    e / (1.0 - e)
+}
+
+/// Bound the error of a point found as `r.at(s)`, for a contour position accurate
+/// to about `n` rounding errors, such as one from a ray-plane intersection.
+pub fn ray_point_error(r: &Ray, s: f64, n: u32) -> Vec3 {
+    gamma(n) * (r.origin().abs() + (s * r.direction()).abs())
+}
+
+/// Bound the error of a point after applying `transform` to `point`, which has error `error`.
+pub fn transformed_error(transform: &Affine3, point: Vec3, error: Vec3) -> Vec3 {
+    let m = transform.matrix3;
+    let abs_m = Mat3::from_cols(m.x_axis.abs(), m.y_axis.abs(), m.z_axis.abs());
+    (1.0 + gamma(3)) * (abs_m * error)
+        + gamma(3) * (abs_m * point.abs() + transform.translation.abs())
+}
+
+/// Place a point given in the local frame of an object into world space as
+/// `origin + onb.to_world(local)`, with an error bound for a local point accurate
+/// to about `n` rounding errors in each coordinate.
+pub(crate) fn frame_point(onb: &ONB, origin: Vec3, local: Vec3, n: u32) -> (Vec3, Vec3) {
+    let transform =
+        Affine3::from_mat3_translation(Mat3::from_cols(onb.u(), onb.v(), onb.w()), origin);
+    let error = transformed_error(&transform, local, gamma(n) * local.abs());
+    (origin + onb.to_world(local), error)
+}
+
+/// Move a point found near a sphere radially onto its surface, with an error bound.
+///
+/// Reprojecting removes the error of the contour position, which can be large
+/// for rays grazing the surface.
+pub(crate) fn sphere_point(center: Vec3, radius: f64, point: Vec3) -> (Vec3, Vec3) {
+    let offset = point - center;
+    let offset = offset * (radius / offset.length());
+    (center + offset, gamma(6) * offset.abs() + gamma(1) * center.abs())
+}
+
+/// Move a point found near a plane through `origin` with unit `normal` onto the plane
+/// along the normal, with an error bound.
+pub(crate) fn plane_point(origin: Vec3, normal: Vec3, point: Vec3) -> (Vec3, Vec3) {
+    let point = point - (point - origin).dot(normal) * normal;
+    (point, gamma(7) * (point.abs() + origin.abs()))
+}
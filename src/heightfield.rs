@@ -0,0 +1,228 @@
+use std::sync::Arc;
+
+use crate::{
+    aabb::AABB,
+    hit::{Hit, HitRecord},
+    material::Material,
+    ray::Ray,
+    triangle::{intersect_triangle, padded_bounding_box, set_triangle_frame},
+    vec::Vec3,
+};
+
+/// Terrain given by a regular grid of heights over a rectangle in the XZ plane.
+///
+/// Each grid cell is split into two triangles, and rays walk the cells they cross
+/// in order, so only a line of cells is tested rather than the whole grid.
+/// Normals are interpolated from per-sample normals, and the texture coordinates
+/// span the whole terrain with `v` running from 1 at the first row to 0 at the last,
+/// so an image used for the heights lines up when also used as a texture.
+///
+/// ```
+/// use raytracing_rust::{
+///     color::Color, heightfield::Heightfield, hit::Hit, material::Lambertian, ray::Ray,
+///     vec::Vec3,
+/// };
+///
+/// // A ramp rising along X, 4 by 4 units wide and 1 unit high
+/// let heights = vec![0.0, 0.5, 1.0, 0.0, 0.5, 1.0];
+/// let material = Lambertian::from(Color::new(0.5, 0.5, 0.5));
+/// let terrain = Heightfield::from_grid(heights, 3, Vec3::ZERO, 4.0, 4.0, 1.0, material);
+///
+/// let r = Ray::new(Vec3::new(3.0, 5.0, 2.0), Vec3::new(0.0, -1.0, 0.0), 0.0);
+/// let rec = terrain.hit(&r, 0.001, f64::INFINITY).unwrap();
+/// assert!((rec.point.y - 0.75).abs() < 1e-9);
+/// assert!((rec.u - 0.75).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Heightfield {
+    heights: Arc<Vec<f64>>,
+    normals: Arc<Vec<Vec3>>,
+    columns: usize,
+    rows: usize,
+    corner: Vec3,
+    x_extent: f64,
+    z_extent: f64,
+    height_scale: f64,
+    bbox: AABB,
+    material: Material,
+}
+
+impl Heightfield {
+    /// Create a terrain from heights in rows of `columns` samples, running along X,
+    /// with successive rows running along Z.
+    ///
+    /// The grid spans `x_extent` and `z_extent` from the `corner`,
+    /// and each height is scaled by `height_scale` above the corner.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the heights do not fill whole rows of `columns` samples,
+    /// or make fewer than two rows or columns.
+    pub fn from_grid(
+        heights: Vec<f64>,
+        columns: usize,
+        corner: Vec3,
+        x_extent: f64,
+        z_extent: f64,
+        height_scale: f64,
+        material: impl Into<Material>,
+    ) -> Self {
+        assert!(columns >= 2, "A heightfield needs at least two columns.");
+        assert!(
+            heights.len().is_multiple_of(columns),
+            "{} heights do not fill whole rows of {} columns.",
+            heights.len(),
+            columns
+        );
+        let rows = heights.len() / columns;
+        assert!(rows >= 2, "A heightfield needs at least two rows.");
+
+        let mut field = Self {
+            heights: Arc::new(heights),
+            normals: Arc::new(Vec::new()),
+            columns,
+            rows,
+            corner,
+            x_extent,
+            z_extent,
+            height_scale,
+            bbox: AABB::new(corner, corner),
+            material: material.into(),
+        };
+
+        // Estimate the normal at each sample from the slopes to its neighbours
+        let mut normals = Vec::with_capacity(columns * rows);
+        for j in 0..rows {
+            for i in 0..columns {
+                let (i0, i1) = (i.saturating_sub(1), (i + 1).min(columns - 1));
+                let (j0, j1) = (j.saturating_sub(1), (j + 1).min(rows - 1));
+                let dx = field.vertex(i1, j).x - field.vertex(i0, j).x;
+                let dz = field.vertex(i, j1).z - field.vertex(i, j0).z;
+                let slope_x = (field.vertex(i1, j).y - field.vertex(i0, j).y) / dx;
+                let slope_z = (field.vertex(i, j1).y - field.vertex(i, j0).y) / dz;
+                normals.push(Vec3::new(-slope_x, 1.0, -slope_z).normalize());
+            }
+        }
+        field.normals = Arc::new(normals);
+
+        let vertices: Vec<Vec3> = (0..rows)
+            .flat_map(|j| (0..columns).map(move |i| (i, j)))
+            .map(|(i, j)| field.vertex(i, j))
+            .collect();
+        field.bbox = padded_bounding_box(&vertices);
+
+        field
+    }
+
+    /// Create a terrain from the brightness of a grayscale image, with one sample per pixel
+    /// and the top row of the image at the corner.
+    pub fn from_image(
+        filepath: &str,
+        corner: Vec3,
+        x_extent: f64,
+        z_extent: f64,
+        height_scale: f64,
+        material: impl Into<Material>,
+    ) -> image::ImageResult<Self> {
+        let img = image::open(filepath)?.into_luma16();
+        let columns = img.width() as usize;
+        let heights = img.pixels().map(|p| p.0[0] as f64 / u16::MAX as f64).collect();
+        Ok(Self::from_grid(heights, columns, corner, x_extent, z_extent, height_scale, material))
+    }
+
+    /// Get the position of the sample in column `i` and row `j`.
+    fn vertex(&self, i: usize, j: usize) -> Vec3 {
+        let x = self.corner.x + self.x_extent * i as f64 / (self.columns - 1) as f64;
+        let z = self.corner.z + self.z_extent * j as f64 / (self.rows - 1) as f64;
+        let y = self.corner.y + self.height_scale * self.heights[j * self.columns + i];
+        Vec3::new(x, y, z)
+    }
+
+    fn vertex_normal(&self, i: usize, j: usize) -> Vec3 {
+        self.normals[j * self.columns + i]
+    }
+
+    /// Intersect the ray with the two triangles of the cell at column `i` and row `j`.
+    fn hit_cell(&self, r: &Ray, i: usize, j: usize, s_min: f64, s_max: f64) -> Option<HitRecord> {
+        let corners = [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)];
+        let mut closest = None;
+        let mut closest_s = s_max;
+
+        for [a, b, c] in [[0, 1, 2], [0, 2, 3]] {
+            let ids = [corners[a], corners[b], corners[c]];
+            let vertices = ids.map(|(i, j)| self.vertex(i, j));
+            if let Some((s, b1, b2)) = intersect_triangle(r, vertices, s_min, closest_s) {
+                closest_s = s;
+                closest = Some((s, ids, b1, b2));
+            }
+        }
+
+        let (s, ids, b1, b2) = closest?;
+        let point = r.at(s);
+        let u = (point.x - self.corner.x) / self.x_extent;
+        let v = 1.0 - (point.z - self.corner.z) / self.z_extent;
+
+        let mut rec = HitRecord::new(s, u, v, point, self.material.clone());
+        let vertices = ids.map(|(i, j)| self.vertex(i, j));
+        let normals = ids.map(|(i, j)| self.vertex_normal(i, j));
+        set_triangle_frame(&mut rec, r, vertices, Some(normals), None, (b1, b2));
+        rec.set_tangent(Vec3::X);
+        Some(rec)
+    }
+}
+
+impl Hit for Heightfield {
+    fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<HitRecord> {
+        let (s_start, s_end) = self.bbox.clip(r, s_min, s_max)?;
+
+        // Walk the grid cells under the ray in order with a 2D DDA over X and Z
+        let cells = [self.columns - 1, self.rows - 1];
+        let cell_size = [self.x_extent / cells[0] as f64, self.z_extent / cells[1] as f64];
+        let origin = [r.origin().x - self.corner.x, r.origin().z - self.corner.z];
+        let direction = [r.direction().x, r.direction().z];
+        let entry = [origin[0] + s_start * direction[0], origin[1] + s_start * direction[1]];
+
+        let mut cell = [0; 2];
+        let mut step = [0_isize; 2];
+        let mut s_next = [f64::INFINITY; 2];
+        let mut s_delta = [f64::INFINITY; 2];
+        for k in 0..2 {
+            let idx = (entry[k] / cell_size[k]).floor().clamp(0.0, (cells[k] - 1) as f64);
+            cell[k] = idx as usize;
+
+            if direction[k] > 0.0 {
+                step[k] = 1;
+                s_delta[k] = cell_size[k] / direction[k];
+                s_next[k] = ((idx + 1.0) * cell_size[k] - origin[k]) / direction[k];
+            } else if direction[k] < 0.0 {
+                step[k] = -1;
+                s_delta[k] = -cell_size[k] / direction[k];
+                s_next[k] = (idx * cell_size[k] - origin[k]) / direction[k];
+            }
+        }
+
+        loop {
+            // Triangles lie within their cell, so the first hit found is the closest.
+            // Test against the full interval, as a hit can sit right on the clipped ends
+            if let Some(rec) = self.hit_cell(r, cell[0], cell[1], s_min, s_max) {
+                return Some(rec);
+            }
+
+            let k = if s_next[0] < s_next[1] { 0 } else { 1 };
+            if s_next[k] > s_end {
+                return None;
+            }
+
+            let next = cell[k] as isize + step[k];
+            if next < 0 || next >= cells[k] as isize {
+                return None;
+            }
+            cell[k] = next as usize;
+            s_next[k] += s_delta[k];
+        }
+    }
+
+    fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<AABB> {
+        Some(self.bbox)
+    }
+}
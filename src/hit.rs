@@ -1,27 +1,199 @@
-use crate::{aabb::AABB, material::Material, ray::Ray, vec::Vec3};
+use crate::{
+    aabb::AABB,
+    color::Color,
+    float,
+    material::Material,
+    ray::Ray,
+    vec::{Affine3, Vec3},
+};
 
+/// Record of a ray intersecting an object.
+///
+/// The `normal` is the shading normal used for scattering, which may be smoothed
+/// across a surface, while `geometric_normal` is the normal of the true surface.
+/// Both face against the ray, and `tangent` and `bitangent` complete an orthonormal
+/// shading frame with the shading normal.
+///
+/// The `error` bounds the rounding error of `point` on each axis, so rays spawned
+/// from the hit can start just far enough off the surface not to hit it again.
 #[derive(Clone, Debug)]
 pub struct HitRecord {
     pub s: f64,
     pub u: f64,
     pub v: f64,
     pub point: Vec3,
+    /// Conservative bound on the absolute error of `point` in each coordinate.
+    pub error: Vec3,
     pub material: Material,
     pub normal: Vec3,
+    pub geometric_normal: Vec3,
+    pub tangent: Vec3,
+    pub bitangent: Vec3,
     pub front_face: bool,
+    /// Color interpolated from per-vertex colors, for objects that carry them.
+    pub vertex_color: Option<Color>,
 }
 
 impl HitRecord {
     pub fn new(s: f64, u: f64, v: f64, point: Vec3, material: Material) -> Self {
-        Self { s, u, v, point, material, normal: Vec3::ZERO, front_face: false }
+        Self {
+            s,
+            u,
+            v,
+            point,
+            error: Vec3::ZERO,
+            material,
+            normal: Vec3::ZERO,
+            geometric_normal: Vec3::ZERO,
+            tangent: Vec3::ZERO,
+            bitangent: Vec3::ZERO,
+            front_face: false,
+            vertex_color: None,
+        }
     }
 
+    /// Set the geometric and shading normals from the outward normal of the surface,
+    /// with an arbitrary tangent frame around it.
     pub fn set_face_normal(&mut self, r: &Ray, outward_normal: Vec3) {
         self.front_face = r.direction().dot(outward_normal) < 0.0;
         self.normal = if self.front_face { outward_normal } else { -1.0 * outward_normal };
+        self.geometric_normal = self.normal;
+        self.set_tangent(Vec3::ZERO);
+    }
+
+    /// Set a shading normal that differs from the geometric one, such as a normal
+    /// interpolated from vertex normals. This must follow `set_face_normal`.
+    ///
+    /// A shading normal bent past the geometric surface is replaced by the geometric normal.
+    pub fn set_shading_normal(&mut self, outward_normal: Vec3) {
+        let normal = if self.front_face { outward_normal } else { -1.0 * outward_normal };
+        self.normal =
+            if normal.dot(self.geometric_normal) > 0.0 { normal } else { self.geometric_normal };
+        self.set_tangent(self.tangent);
+    }
+
+    /// Align the tangent of the shading frame as closely as possible with `tangent`,
+    /// such as the direction of increasing `u`, or pick any tangent if it is zero
+    /// or parallel to the shading normal.
+    pub fn set_tangent(&mut self, tangent: Vec3) {
+        let tangent = (tangent - tangent.dot(self.normal) * self.normal).normalize_or_zero();
+        self.tangent =
+            if tangent == Vec3::ZERO { self.normal.any_orthonormal_vector() } else { tangent };
+        self.bitangent = self.normal.cross(self.tangent);
+    }
+
+    /// Map the normals and shading frame through a change of space, keeping the facing of the hit.
+    ///
+    /// Normals go through `normal_map`, which for an affine transform is its inverse transpose,
+    /// while tangents go through `tangent_map`, the linear part of the transform itself.
+    pub fn transform_frame(
+        &mut self,
+        normal_map: impl Fn(Vec3) -> Vec3,
+        tangent_map: impl Fn(Vec3) -> Vec3,
+    ) {
+        self.geometric_normal = normal_map(self.geometric_normal).normalize();
+        self.normal = normal_map(self.normal).normalize();
+        self.set_tangent(tangent_map(self.tangent));
+    }
+
+    /// Move the hit point through an affine transform, growing its error bound
+    /// by the rounding of the transform.
+    pub fn transform_point(&mut self, transform: &Affine3) {
+        self.error = float::transformed_error(transform, self.point, self.error);
+        self.point = transform.transform_point3(self.point);
+    }
+
+    /// Get the origin for a ray leaving the hit in `direction`, offset along the
+    /// geometric normal past the error bounds of the point, to the side the ray leaves on.
+    pub fn spawn_origin(&self, direction: Vec3) -> Vec3 {
+        let n = self.geometric_normal;
+        let side = if direction.dot(n) < 0.0 { -n } else { n };
+
+        // Round away from the surface, so the origin leaves it even when the error is zero
+        let mut origin = self.point + n.abs().dot(self.error) * side;
+        for i in 0..3 {
+            if side[i] > 0.0 {
+                origin[i] = origin[i].next_up();
+            } else if side[i] < 0.0 {
+                origin[i] = origin[i].next_down();
+            }
+        }
+        origin
+    }
+
+    /// Spawn a ray leaving the hit in `direction` at `time`, which cannot hit the same surface
+    /// again at the point it leaves.
+    pub fn spawn_ray(&self, direction: Vec3, time: f64) -> Ray {
+        Ray::new(self.spawn_origin(direction), direction, time)
+    }
+
+    /// Get the contour position along `r` just past the error bounds of this hit,
+    /// from which to keep searching the same ray for further hits.
+    pub fn s_beyond(&self, r: &Ray) -> f64 {
+        let n = self.geometric_normal;
+        let slope = n.dot(r.direction()).abs();
+        let offset = if slope > 0.0 { n.abs().dot(self.error) / slope } else { 0.0 };
+        (self.s + offset).next_up()
+    }
+
+    /// Convert a direction from the shading frame, with Z along the shading normal, to world space.
+    pub fn shading_to_world(&self, v: Vec3) -> Vec3 {
+        v.x * self.tangent + v.y * self.bitangent + v.z * self.normal
     }
 }
 
+/// A span of a ray inside a solid object, between the hits where the ray enters and exits it.
+///
+/// A missing hit means the span extends to infinity in that direction.
+#[derive(Clone, Debug)]
+pub struct Interval {
+    pub enter: Option<HitRecord>,
+    pub exit: Option<HitRecord>,
+}
+
+impl Interval {
+    pub fn start(&self) -> f64 {
+        self.enter.as_ref().map_or(f64::NEG_INFINITY, |rec| rec.s)
+    }
+
+    pub fn end(&self) -> f64 {
+        self.exit.as_ref().map_or(f64::INFINITY, |rec| rec.s)
+    }
+}
+
+/// Find the spans along the whole line of the ray where it is inside `object`, in order,
+/// by repeatedly hitting the object from just past the previous hit.
+///
+/// Hits facing the ray enter the object and hits facing away exit it,
+/// which is only meaningful for closed objects with outward normals.
+pub fn probe_intervals(object: &impl Hit, r: &Ray) -> Vec<Interval> {
+    let mut intervals = Vec::new();
+    let mut enter = None;
+    let mut inside = false;
+
+    let mut s_min = f64::NEG_INFINITY;
+    while let Some(rec) = object.hit(r, s_min, f64::INFINITY) {
+        s_min = rec.s_beyond(r);
+
+        if rec.front_face {
+            if !inside {
+                enter = Some(rec);
+                inside = true;
+            }
+        } else if inside || intervals.is_empty() {
+            intervals.push(Interval { enter: enter.take(), exit: Some(rec) });
+            inside = false;
+        }
+    }
+
+    if inside {
+        intervals.push(Interval { enter, exit: None });
+    }
+
+    intervals
+}
+
+/// Trait for objects that can be intersected by a ray.
 pub trait Hit {
     /// Get a `HitRecord` for the ray and the object within the
     /// contour position interval `s_min` and `s_max`.
@@ -29,4 +201,12 @@ pub trait Hit {
 
     /// Determine a bounding `AABB` for the object between time `time0` and `time1`.
     fn bounding_box(&self, time0: f64, time1: f64) -> Option<AABB>;
+
+    /// Find the spans along the whole line of the ray where it is inside the object, in order.
+    fn intervals(&self, r: &Ray) -> Vec<Interval>
+    where
+        Self: Sized,
+    {
+        probe_intervals(self, r)
+    }
 }
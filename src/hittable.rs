@@ -3,14 +3,32 @@ use std::ops::Deref;
 use crate::{
     aabb::AABB,
     bvh::BVH,
+    capsule::Capsule,
+    cone::Cone,
+    csg::Csg,
     cuboid::Cuboid,
-    hit::{Hit, HitRecord},
+    curve::Curves,
+    cylinder::Cylinder,
+    disk::Disk,
+    heightfield::Heightfield,
+    hit::{probe_intervals, Hit, HitRecord, Interval},
+    instance::Instance,
     medium::ConstantMedium,
+    mesh::TriangleMesh,
+    motion::Motion,
+    particles::ParticleSet,
+    plane::Plane,
+    quad::Quad,
     ray::Ray,
     rectangle::{XYRectangle, XZRectangle, YZRectangle},
     rotate::RotateY,
+    sdf::DistanceField,
     sphere::Sphere,
+    stats,
+    torus::Torus,
+    transform::Transform,
     translate::Translate,
+    triangle::Triangle,
 };
 
 /// Enumeration of objects that can be hit by a ray.
@@ -25,6 +43,74 @@ pub enum Hittable {
     RotateY(RotateY),
     ConstantMedium(ConstantMedium),
     BVH(BVH),
+    Triangle(Triangle),
+    TriangleMesh(TriangleMesh),
+    Quad(Quad),
+    Disk(Disk),
+    Cylinder(Cylinder),
+    Cone(Cone),
+    Capsule(Capsule),
+    Torus(Torus),
+    Plane(Plane),
+    Csg(Csg),
+    DistanceField(DistanceField),
+    Heightfield(Heightfield),
+    Curves(Curves),
+    Instance(Instance),
+    Motion(Motion),
+    ParticleSet(ParticleSet),
+    Transform(Transform),
+}
+
+impl Hittable {
+    /// Determine if the variant tests rays against geometry itself,
+    /// rather than passing them on to other hittables.
+    pub fn is_primitive(&self) -> bool {
+        !matches!(
+            self,
+            Self::Cuboid(_)
+                | Self::Translate(_)
+                | Self::RotateY(_)
+                | Self::ConstantMedium(_)
+                | Self::BVH(_)
+                | Self::Csg(_)
+                | Self::Instance(_)
+                | Self::Motion(_)
+                | Self::Transform(_)
+        )
+    }
+
+    /// Get the name of the variant, such as `"Sphere"`.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            Self::Sphere(_) => "Sphere",
+            Self::XYRectangle(_) => "XYRectangle",
+            Self::XZRectangle(_) => "XZRectangle",
+            Self::YZRectangle(_) => "YZRectangle",
+            Self::Cuboid(_) => "Cuboid",
+            Self::Translate(_) => "Translate",
+            Self::RotateY(_) => "RotateY",
+            Self::ConstantMedium(_) => "ConstantMedium",
+            Self::BVH(_) => "BVH",
+            Self::Triangle(_) => "Triangle",
+            Self::TriangleMesh(_) => "TriangleMesh",
+            Self::Quad(_) => "Quad",
+            Self::Disk(_) => "Disk",
+            Self::Cylinder(_) => "Cylinder",
+            Self::Cone(_) => "Cone",
+            Self::Capsule(_) => "Capsule",
+            Self::Torus(_) => "Torus",
+            Self::Plane(_) => "Plane",
+            Self::Csg(_) => "Csg",
+            Self::DistanceField(_) => "DistanceField",
+            Self::Heightfield(_) => "Heightfield",
+            Self::Curves(_) => "Curves",
+            Self::Instance(_) => "Instance",
+            Self::Motion(_) => "Motion",
+            Self::ParticleSet(_) => "ParticleSet",
+            Self::Transform(_) => "Transform",
+        }
+    }
 }
 
 impl From<Sphere> for Hittable {
@@ -81,8 +167,112 @@ impl From<BVH> for Hittable {
     }
 }
 
+impl From<Triangle> for Hittable {
+    fn from(inner: Triangle) -> Self {
+        Self::Triangle(inner)
+    }
+}
+
+impl From<TriangleMesh> for Hittable {
+    fn from(inner: TriangleMesh) -> Self {
+        Self::TriangleMesh(inner)
+    }
+}
+
+impl From<Quad> for Hittable {
+    fn from(inner: Quad) -> Self {
+        Self::Quad(inner)
+    }
+}
+
+impl From<Disk> for Hittable {
+    fn from(inner: Disk) -> Self {
+        Self::Disk(inner)
+    }
+}
+
+impl From<Cylinder> for Hittable {
+    fn from(inner: Cylinder) -> Self {
+        Self::Cylinder(inner)
+    }
+}
+
+impl From<Cone> for Hittable {
+    fn from(inner: Cone) -> Self {
+        Self::Cone(inner)
+    }
+}
+
+impl From<Capsule> for Hittable {
+    fn from(inner: Capsule) -> Self {
+        Self::Capsule(inner)
+    }
+}
+
+impl From<Torus> for Hittable {
+    fn from(inner: Torus) -> Self {
+        Self::Torus(inner)
+    }
+}
+
+impl From<Plane> for Hittable {
+    fn from(inner: Plane) -> Self {
+        Self::Plane(inner)
+    }
+}
+
+impl From<Csg> for Hittable {
+    fn from(inner: Csg) -> Self {
+        Self::Csg(inner)
+    }
+}
+
+impl From<DistanceField> for Hittable {
+    fn from(inner: DistanceField) -> Self {
+        Self::DistanceField(inner)
+    }
+}
+
+impl From<Heightfield> for Hittable {
+    fn from(inner: Heightfield) -> Self {
+        Self::Heightfield(inner)
+    }
+}
+
+impl From<Curves> for Hittable {
+    fn from(inner: Curves) -> Self {
+        Self::Curves(inner)
+    }
+}
+
+impl From<Instance> for Hittable {
+    fn from(inner: Instance) -> Self {
+        Self::Instance(inner)
+    }
+}
+
+impl From<Motion> for Hittable {
+    fn from(inner: Motion) -> Self {
+        Self::Motion(inner)
+    }
+}
+
+impl From<ParticleSet> for Hittable {
+    fn from(inner: ParticleSet) -> Self {
+        Self::ParticleSet(inner)
+    }
+}
+
+impl From<Transform> for Hittable {
+    fn from(inner: Transform) -> Self {
+        Self::Transform(inner)
+    }
+}
+
 impl Hit for Hittable {
     fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<HitRecord> {
+        stats::record_primitive_test(self);
+
         match self {
             Self::Sphere(inner) => inner.hit(r, s_min, s_max),
             Self::XYRectangle(inner) => inner.hit(r, s_min, s_max),
@@ -93,6 +283,23 @@ impl Hit for Hittable {
             Self::RotateY(inner) => inner.hit(r, s_min, s_max),
             Self::ConstantMedium(inner) => inner.hit(r, s_min, s_max),
             Self::BVH(inner) => inner.hit(r, s_min, s_max),
+            Self::Triangle(inner) => inner.hit(r, s_min, s_max),
+            Self::TriangleMesh(inner) => inner.hit(r, s_min, s_max),
+            Self::Quad(inner) => inner.hit(r, s_min, s_max),
+            Self::Disk(inner) => inner.hit(r, s_min, s_max),
+            Self::Cylinder(inner) => inner.hit(r, s_min, s_max),
+            Self::Cone(inner) => inner.hit(r, s_min, s_max),
+            Self::Capsule(inner) => inner.hit(r, s_min, s_max),
+            Self::Torus(inner) => inner.hit(r, s_min, s_max),
+            Self::Plane(inner) => inner.hit(r, s_min, s_max),
+            Self::Csg(inner) => inner.hit(r, s_min, s_max),
+            Self::DistanceField(inner) => inner.hit(r, s_min, s_max),
+            Self::Heightfield(inner) => inner.hit(r, s_min, s_max),
+            Self::Curves(inner) => inner.hit(r, s_min, s_max),
+            Self::Instance(inner) => inner.hit(r, s_min, s_max),
+            Self::Motion(inner) => inner.hit(r, s_min, s_max),
+            Self::ParticleSet(inner) => inner.hit(r, s_min, s_max),
+            Self::Transform(inner) => inner.hit(r, s_min, s_max),
         }
     }
 
@@ -107,12 +314,56 @@ impl Hit for Hittable {
             Self::RotateY(inner) => inner.bounding_box(time0, time1),
             Self::ConstantMedium(inner) => inner.bounding_box(time0, time1),
             Self::BVH(inner) => inner.bounding_box(time0, time1),
+            Self::Triangle(inner) => inner.bounding_box(time0, time1),
+            Self::TriangleMesh(inner) => inner.bounding_box(time0, time1),
+            Self::Quad(inner) => inner.bounding_box(time0, time1),
+            Self::Disk(inner) => inner.bounding_box(time0, time1),
+            Self::Cylinder(inner) => inner.bounding_box(time0, time1),
+            Self::Cone(inner) => inner.bounding_box(time0, time1),
+            Self::Capsule(inner) => inner.bounding_box(time0, time1),
+            Self::Torus(inner) => inner.bounding_box(time0, time1),
+            Self::Plane(inner) => inner.bounding_box(time0, time1),
+            Self::Csg(inner) => inner.bounding_box(time0, time1),
+            Self::DistanceField(inner) => inner.bounding_box(time0, time1),
+            Self::Heightfield(inner) => inner.bounding_box(time0, time1),
+            Self::Curves(inner) => inner.bounding_box(time0, time1),
+            Self::Instance(inner) => inner.bounding_box(time0, time1),
+            Self::Motion(inner) => inner.bounding_box(time0, time1),
+            Self::ParticleSet(inner) => inner.bounding_box(time0, time1),
+            Self::Transform(inner) => inner.bounding_box(time0, time1),
+        }
+    }
+
+    fn intervals(&self, r: &Ray) -> Vec<Interval> {
+        match self {
+            // Nested CSG objects combine their children's spans directly, also through instances
+            Self::Csg(inner) => inner.intervals(r),
+            Self::Instance(inner) => inner.intervals(r),
+            Self::Transform(inner) => inner.intervals(r),
+            _ => probe_intervals(self, r),
         }
     }
 }
 
 /// Container for a collection of hittable objects.
-#[derive(Clone, Debug)]
+///
+/// ```
+/// use raytracing_rust::{
+///     color::Color, hit::Hit, hittable::HittableList, material::Lambertian, ray::Ray,
+///     sphere::Sphere, vec::Vec3,
+/// };
+///
+/// let mut world = HittableList::new();
+/// let material = Lambertian::from(Color::new(0.5, 0.5, 0.5));
+/// world.push(Sphere::stationary(Vec3::new(0.0, 0.0, -5.0), 1.0, material.clone()));
+/// world.push(Sphere::stationary(Vec3::new(0.0, 0.0, -10.0), 1.0, material));
+///
+/// // The closest of the two spheres is reported
+/// let r = Ray::new(Vec3::ZERO, Vec3::new(0.0, 0.0, -1.0), 0.0);
+/// let rec = world.hit(&r, 0.001, f64::INFINITY).unwrap();
+/// assert!((rec.s - 4.0).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug, Default)]
 pub struct HittableList {
     objects: Vec<Hittable>,
 }
@@ -138,6 +389,10 @@ impl HittableList {
         self.objects.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
     pub fn push(&mut self, object: impl Into<Hittable>) {
         self.objects.push(object.into());
     }
@@ -175,7 +430,7 @@ impl<'a> IntoIterator for &'a HittableList {
     type IntoIter = std::slice::Iter<'a, Hittable>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.objects.as_slice().into_iter()
+        self.objects.iter()
     }
 }
 
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use crate::{
+    aabb::AABB,
+    hit::{Hit, HitRecord, Interval},
+    hittable::Hittable,
+    ray::Ray,
+    transform::AffineMap,
+    vec::Affine3,
+};
+
+/// A placement of shared geometry under an affine transform.
+///
+/// Many instances can refer to the same object, so repeating a large mesh costs
+/// only one copy of the mesh plus a small transform per instance. Rays are moved
+/// into the object's own space for intersection and the hit is moved back, with
+/// normals transformed by the inverse transpose so they stay perpendicular to the surface.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use raytracing_rust::{
+///     color::Color, hit::Hit, hittable::Hittable, instance::Instance, material::Lambertian,
+///     ray::Ray, sphere::Sphere, vec::{Affine3, Vec3},
+/// };
+///
+/// let material = Lambertian::from(Color::new(0.5, 0.5, 0.5));
+/// let ball = Arc::new(Hittable::from(Sphere::stationary(Vec3::ZERO, 1.0, material)));
+///
+/// // Two copies of the same sphere, one of them moved and stretched along Z
+/// let here = Instance::new(ball.clone(), Affine3::IDENTITY);
+/// let there = Instance::new(
+///     ball,
+///     Affine3::from_scale_rotation_translation(
+///         Vec3::new(1.0, 1.0, 2.0),
+///         Default::default(),
+///         Vec3::new(5.0, 0.0, 0.0),
+///     ),
+/// );
+///
+/// let r = Ray::new(Vec3::new(5.0, 0.0, 10.0), Vec3::new(0.0, 0.0, -1.0), 0.0);
+/// assert!(here.hit(&r, 0.001, f64::INFINITY).is_none());
+/// let rec = there.hit(&r, 0.001, f64::INFINITY).unwrap();
+/// assert!((rec.point.z - 2.0).abs() < 1e-9 && (rec.normal.z - 1.0).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Instance {
+    object: Arc<Hittable>,
+    map: AffineMap,
+}
+
+impl Instance {
+    /// Place the shared `object` in the scene, mapping its own space into world space by `transform`.
+    pub fn new(object: Arc<Hittable>, transform: Affine3) -> Self {
+        Self { object, map: AffineMap::new(transform) }
+    }
+
+    pub fn object(&self) -> &Arc<Hittable> {
+        &self.object
+    }
+
+    pub fn transform(&self) -> &Affine3 {
+        self.map.transform()
+    }
+}
+
+impl Hit for Instance {
+    fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<HitRecord> {
+        let rec = self.object.hit(&self.map.to_object(r), s_min, s_max)?;
+        Some(self.map.to_world(rec))
+    }
+
+    fn bounding_box(&self, time0: f64, time1: f64) -> Option<AABB> {
+        self.object.bounding_box(time0, time1).map(|bbox| bbox.transformed(self.transform()))
+    }
+
+    fn intervals(&self, r: &Ray) -> Vec<Interval> {
+        self.map.intervals_to_world(self.object.intervals(&self.map.to_object(r)))
+    }
+}
@@ -0,0 +1,124 @@
+//! The miniseries [Ray Tracing in One Weekend](https://raytracing.github.io/),
+//! implemented in the Rust programming language.
+//!
+//! A scene is built by pushing objects into a [`HittableList`](hittable::HittableList),
+//! and a [`Renderer`](render::Renderer) turns it into a [`Framebuffer`](render::Framebuffer)
+//! using the camera described by a set of [`ViewOptions`](camera::ViewOptions).
+//!
+//! ```
+//! use raytracing_rust::{
+//!     bvh::BVH,
+//!     camera::ViewOptions,
+//!     color::Color,
+//!     hittable::HittableList,
+//!     material::{Dielectric, Lambertian},
+//!     render::Renderer,
+//!     sphere::Sphere,
+//!     vec::Vec3,
+//! };
+//!
+//! let mut spheres = HittableList::new();
+//! let ground = Lambertian::from(Color::new(0.5, 0.5, 0.5));
+//! spheres.push(Sphere::stationary(Vec3::new(0.0, -1000.0, 0.0), 1000.0, ground));
+//! spheres.push(Sphere::stationary(Vec3::new(0.0, 1.0, 0.0), 1.0, Dielectric::new(1.5)));
+//!
+//! let mut world = HittableList::new();
+//! world.push(BVH::new(spheres, 0.0, 1.0));
+//!
+//! let opts = ViewOptions::new().with_image_width(32).with_samples_per_pixel(4);
+//! let fb = Renderer::new(opts).render(&world);
+//! assert_eq!(fb.width(), 32);
+//! ```
+
+#![allow(clippy::upper_case_acronyms, clippy::too_many_arguments, clippy::needless_range_loop)]
+
+/// Axis-aligned bounding boxes.
+pub mod aabb;
+/// Keyframed animation sequences.
+pub mod animation;
+/// Bounding volume hierarchies for accelerating ray intersection.
+pub mod bvh;
+/// Camera and view options.
+pub mod camera;
+/// Capsules.
+pub mod capsule;
+/// Colors and the path tracing integrator.
+pub mod color;
+/// Cones.
+pub mod cone;
+/// Constructive solid geometry.
+pub mod csg;
+/// Axis-aligned boxes.
+pub mod cuboid;
+/// Bézier curves for hair, fur and grass.
+pub mod curve;
+/// Cylinders.
+pub mod cylinder;
+/// Disks.
+pub mod disk;
+/// Displacement of surfaces by textures into meshes.
+pub mod displace;
+/// Floating-point rounding error bounds for robust ray spawning.
+pub mod float;
+/// Height-field terrain.
+pub mod heightfield;
+/// Ray intersection records and the `Hit` trait.
+pub mod hit;
+/// Enumeration and collections of hittable objects.
+pub mod hittable;
+/// Instancing of shared geometry under affine transforms.
+pub mod instance;
+/// Surface materials.
+pub mod material;
+/// Participating media such as smoke and fog.
+pub mod medium;
+/// Indexed triangle meshes.
+pub mod mesh;
+/// Motion blur of objects moving through keyframed poses.
+pub mod motion;
+/// Wavefront OBJ mesh loading.
+pub mod obj;
+/// Orthonormal bases.
+pub mod onb;
+/// Sets of spherical particles stored in flat buffers.
+pub mod particles;
+/// Perlin noise generation.
+pub mod perlin;
+/// Infinite planes.
+pub mod plane;
+/// PLY mesh loading.
+pub mod ply;
+/// Live preview of a render in the terminal.
+pub mod preview;
+/// Parallelograms with arbitrary orientation.
+pub mod quad;
+/// Rays.
+pub mod ray;
+/// Axis-aligned rectangles.
+pub mod rectangle;
+/// Rendering into framebuffers.
+pub mod render;
+/// Polynomial root finding.
+pub mod roots;
+/// Rotation of hittable objects.
+pub mod rotate;
+/// Scene graphs of named nodes with inherited transforms and materials.
+pub mod scene;
+/// Signed distance fields rendered by sphere tracing.
+pub mod sdf;
+/// Spheres.
+pub mod sphere;
+/// Render statistics and profiling counters.
+pub mod stats;
+/// Surface textures.
+pub mod texture;
+/// Tori.
+pub mod torus;
+/// General affine transforms of hittable objects.
+pub mod transform;
+/// Translation of hittable objects.
+pub mod translate;
+/// Triangles.
+pub mod triangle;
+/// Three-dimensional vectors.
+pub mod vec;
@@ -1,40 +1,32 @@
-#![allow(dead_code)]
-
-mod aabb;
-mod bvh;
-mod camera;
-mod color;
-mod cuboid;
-mod hit;
-mod hittable;
-mod material;
-mod medium;
-mod perlin;
-mod ray;
-mod rectangle;
-mod rotate;
-mod sphere;
-mod texture;
-mod translate;
-mod vec;
+use std::{ops::Range, sync::Arc, time::Duration};
 
 use rand::Rng;
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
-
-use crate::bvh::BVH;
-use crate::camera::{Camera, ViewOptions};
-use crate::color::{ray_color, Color, ColorFormat};
-use crate::cuboid::Cuboid;
-use crate::hittable::HittableList;
-use crate::material::{Dielectric, DiffuseLight, Isotropic, Lambertian, Metal};
-use crate::medium::ConstantMedium;
-use crate::perlin::Perlin;
-use crate::rectangle::{XYRectangle, XZRectangle, YZRectangle};
-use crate::rotate::RotateY;
-use crate::sphere::Sphere;
-use crate::texture::{ImageTexture, NoiseTexture, SolidColor};
-use crate::translate::Translate;
-use crate::vec::{Vec3, VecOps};
+
+use raytracing_rust::animation::{Animation, CameraTrack, FrameEvent, Keyframes};
+use raytracing_rust::bvh::BVH;
+use raytracing_rust::camera::ViewOptions;
+use raytracing_rust::color::Color;
+use raytracing_rust::cuboid::Cuboid;
+use raytracing_rust::hit::Hit;
+use raytracing_rust::hittable::{Hittable, HittableList};
+use raytracing_rust::instance::Instance;
+use raytracing_rust::material::{Dielectric, DiffuseLight, Isotropic, Lambertian, Metal};
+use raytracing_rust::medium::ConstantMedium;
+use raytracing_rust::mesh::TriangleMesh;
+use raytracing_rust::obj::load_obj;
+use raytracing_rust::particles::{ParticleData, ParticleSet};
+use raytracing_rust::perlin::Perlin;
+use raytracing_rust::ply::load_ply;
+use raytracing_rust::preview::TerminalPreview;
+use raytracing_rust::rectangle::{XYRectangle, XZRectangle, YZRectangle};
+use raytracing_rust::render::{CancellationToken, RenderProgress, Renderer};
+use raytracing_rust::rotate::RotateY;
+use raytracing_rust::scene::SceneNode;
+use raytracing_rust::sphere::Sphere;
+use raytracing_rust::stats::RenderStats;
+use raytracing_rust::texture::{ImageTexture, NoiseTexture, SolidColor, VertexColorTexture};
+use raytracing_rust::translate::Translate;
+use raytracing_rust::vec::{Affine3, Vec3, VecOps};
 
 fn two_spheres() -> (HittableList, ViewOptions) {
     let texture = NoiseTexture::new(Perlin::new(), 0.5);
@@ -175,16 +167,30 @@ fn final_scene() -> (HittableList, ViewOptions) {
     world.push(metal_sphere);
 
     // Boundary sphere
-    let boundary1 = Sphere::stationary(Vec3::new(360.0, 150.0, 145.0), 70.0, Dielectric::new(1.5));
-    let medium1 =
-        ConstantMedium::new(boundary1.clone(), Isotropic::from(Color::new(0.2, 0.4, 0.9)), 0.2);
-    world.push(boundary1);
+    let boundary1 = Arc::new(Hittable::from(Sphere::stationary(
+        Vec3::new(360.0, 150.0, 145.0),
+        70.0,
+        Dielectric::new(1.5),
+    )));
+    let medium1 = ConstantMedium::new(
+        Instance::new(boundary1.clone(), Affine3::IDENTITY),
+        Isotropic::from(Color::new(0.2, 0.4, 0.9)),
+        0.2,
+    );
+    world.push(Instance::new(boundary1, Affine3::IDENTITY));
     world.push(medium1);
 
-    let boundary2 = Sphere::stationary(Vec3::new(0.0, 0.0, 0.0), 5000.0, Dielectric::new(1.5));
-    let medium2 =
-        ConstantMedium::new(boundary2.clone(), Isotropic::from(Color::new(1.0, 1.0, 1.0)), 1e-4);
-    world.push(boundary2);
+    let boundary2 = Arc::new(Hittable::from(Sphere::stationary(
+        Vec3::new(0.0, 0.0, 0.0),
+        5000.0,
+        Dielectric::new(1.5),
+    )));
+    let medium2 = ConstantMedium::new(
+        Instance::new(boundary2.clone(), Affine3::IDENTITY),
+        Isotropic::from(Color::new(1.0, 1.0, 1.0)),
+        1e-4,
+    );
+    world.push(Instance::new(boundary2, Affine3::IDENTITY));
     world.push(medium2);
 
     // Globe sphere
@@ -201,18 +207,19 @@ fn final_scene() -> (HittableList, ViewOptions) {
 
     // Cube of spheres
     let ns = 1000;
-    let mut boxes2 = HittableList::new();
+    let positions = (0..ns).map(|_| Vec3::random(0.0..165.0)).collect();
     let white = Lambertian::from(Color::new(0.73, 0.73, 0.73));
-    for _ in 0..ns {
-        let sphere = Sphere::stationary(Vec3::random(0.0..165.0), 10.0, white.clone());
-        boxes2.push(sphere);
+    let spheres = ParticleSet::new(ParticleData::uniform(positions, 10.0), white);
+    let sphere_cube = SceneNode::group("sphere_cube")
+        .with_transform(
+            Affine3::from_translation(Vec3::new(-100.0, 270.0, 395.0))
+                * Affine3::from_rotation_y(15f64.to_radians()),
+        )
+        .with_child(SceneNode::object("spheres", spheres));
+    for object in sphere_cube.flatten(0.0, 1.0) {
+        world.push(object);
     }
 
-    let sphere_cube = BVH::new(boxes2, 0.0, 1.0);
-    let sphere_cube = RotateY::new(sphere_cube, 15.0);
-    let sphere_cube = Translate::new(sphere_cube, Vec3::new(-100.0, 270.0, 395.0));
-    world.push(sphere_cube);
-
     // Image view options
     let opts = ViewOptions::new()
         .with_apsect_ratio(1.0)
@@ -226,44 +233,189 @@ fn final_scene() -> (HittableList, ViewOptions) {
     (world, opts)
 }
 
-fn main() {
-    // World & view options
-    let (world, opts) = final_scene();
+fn mesh_model(path: &str) -> (HittableList, ViewOptions) {
+    let data = if path.to_ascii_lowercase().ends_with(".ply") {
+        load_ply(path).expect("Failed to load the PLY model.")
+    } else {
+        load_obj(path).expect("Failed to load the OBJ model.")
+    };
+
+    // Show vertex colors when the model has them, and a plain grey otherwise
+    let albedo = VertexColorTexture::new(SolidColor::new(Color::new(0.73, 0.73, 0.73)));
+    let mesh = TriangleMesh::new(data, Lambertian::new(albedo));
+
+    // Frame the camera around the model, whatever its size and position
+    let bbox = mesh.bounding_box(0.0, 1.0).expect("Cannot construct bounding box for the model.");
+    let center = 0.5 * (bbox.min() + bbox.max());
+    let size = (bbox.max() - bbox.min()).length();
+
+    let ground = Lambertian::from(Color::new(0.4, 0.5, 0.4));
+    let floor = Sphere::stationary(
+        Vec3::new(center.x, bbox.min().y - 1000.0 * size, center.z),
+        1000.0 * size,
+        ground,
+    );
 
-    // Camera
-    let cam = Camera::from_options(&opts);
+    let mut world = HittableList::new();
+    world.push(mesh);
+    world.push(floor);
 
-    println!("P3");
-    println!("{} {}", opts.image_width, opts.image_height);
-    println!("255");
+    let lookfrom = center + size * Vec3::new(0.8, 0.4, 1.2);
+    let opts = ViewOptions::new()
+        .with_background(Color::new(0.7, 0.8, 1.0))
+        .with_lookfrom(lookfrom)
+        .with_lookat(center)
+        .with_vfov(30.0)
+        .with_aperture(0.0)
+        .with_focus_dist((lookfrom - center).length());
 
-    for j in (0..opts.image_height).rev() {
-        eprintln!("Scanlines remaining: {}", j + 1);
+    (world, opts)
+}
 
-        let scanline: Vec<Color> = (0..opts.image_width)
-            .into_par_iter()
-            .map(|i| {
-                let mut pixel_color = Color::new(0.0, 0.0, 0.0);
-                for _ in 0..opts.samples_per_pixel {
-                    let mut rng = rand::thread_rng();
-                    let random_u: f64 = rng.gen();
-                    let random_v: f64 = rng.gen();
+fn bouncing_spheres(shutter: Range<f64>) -> HittableList {
+    let ground = Lambertian::from(Color::new(0.5, 0.5, 0.5));
+    let floor = Sphere::stationary(Vec3::new(0.0, -1000.0, 0.0), 1000.0, ground);
 
-                    let u = ((i as f64) + random_u) / ((opts.image_width - 1) as f64);
-                    let v = ((j as f64) + random_v) / ((opts.image_height - 1) as f64);
+    // Each sphere bounces once per second with a different phase
+    let mut world = HittableList::new();
+    world.push(floor);
+
+    for (i, x) in [-4.0, 0.0, 4.0].into_iter().enumerate() {
+        let phase = i as f64 / 3.0;
+        let mut centers = Keyframes::new(Vec3::new(x, 1.0, 0.0));
+        for bounce in 0..4 {
+            let t = bounce as f64 + phase;
+            centers = centers
+                .with_key(t, Vec3::new(x, 1.0, 0.0))
+                .with_key(t + 0.5, Vec3::new(x, 3.0, 0.0));
+        }
 
-                    let r = cam.get_ray(u, v);
-                    pixel_color += ray_color(&r, &world, opts.background, opts.max_depth);
-                }
+        let material =
+            Lambertian::from(Color::new(0.2 + 0.3 * i as f64, 0.3, 0.7 - 0.2 * i as f64));
+        world.push(Sphere::keyframed(&centers, shutter.clone(), 1.0, material));
+    }
+
+    world
+}
+
+fn bouncing_spheres_animation() -> Animation {
+    let opts = ViewOptions::new().with_lookat(Vec3::new(0.0, 1.5, 0.0)).with_samples_per_pixel(50);
+
+    let camera = CameraTrack::from_options(&opts)
+        .with_lookfrom_key(0.0, Vec3::new(13.0, 2.0, 3.0))
+        .with_lookfrom_key(3.0, Vec3::new(3.0, 4.0, 13.0))
+        .with_vfov_key(0.0, 30.0)
+        .with_vfov_key(3.0, 40.0)
+        .with_focus_dist_key(0.0, 13.5)
+        .with_focus_dist_key(3.0, 13.8);
+
+    Animation::new(72, 24.0, opts).with_camera(camera)
+}
 
-                pixel_color
+/// Get the value following a command line flag, e.g. `--flag value`.
+fn arg_value(flag: &str) -> Option<String> {
+    let args: Vec<String> = std::env::args().collect();
+    args.iter().position(|arg| arg == flag).and_then(|idx| args.get(idx + 1).cloned())
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{:02}:{:02}:{:02}", secs / 3600, (secs / 60) % 60, secs % 60)
+}
+
+fn draw_progress_bar(progress: &RenderProgress) {
+    const BAR_WIDTH: usize = 40;
+    let filled = ((progress.fraction * BAR_WIDTH as f64) as usize).min(BAR_WIDTH);
+    let eta = progress.eta.map(format_duration).unwrap_or_else(|| "--:--:--".to_string());
+
+    eprint!(
+        "\r[{}{}] {:5.1}% | {:.2} Msamples/s | elapsed {} | ETA {}",
+        "#".repeat(filled),
+        " ".repeat(BAR_WIDTH - filled),
+        100.0 * progress.fraction,
+        progress.samples_per_sec / 1e6,
+        format_duration(progress.elapsed),
+        eta,
+    );
+}
+
+fn report_stats() {
+    let stats = RenderStats::collect();
+    eprint!("{}", stats);
+
+    if let Some(path) = arg_value("--stats-json") {
+        std::fs::write(&path, stats.to_json()).expect("Failed to write render statistics.");
+    }
+}
+
+fn main() {
+    if std::env::args().any(|arg| arg == "--animate") {
+        let animation = bouncing_spheres_animation();
+        let frames = animation.frames;
+        animation
+            .render_frames("frames", bouncing_spheres, |event| match event {
+                FrameEvent::Skipped { frame, path } => {
+                    eprintln!("Skipping frame {}: {} already exists", frame, path.display())
+                }
+                FrameEvent::Rendering { frame, .. } => {
+                    eprintln!("Rendering frame {} of {}", frame + 1, frames)
+                }
             })
-            .collect();
+            .expect("Failed to write animation frames.");
+        report_stats();
+        eprintln!("Done.");
+        return;
+    }
 
-        for pixel_color in scanline {
-            println!("{}", pixel_color.format_color(opts.samples_per_pixel));
+    // World & view options
+    let (world, opts) = match arg_value("--scene").as_deref() {
+        Some("two_spheres") => two_spheres(),
+        Some("earth") => earth(),
+        Some("simple_light") => simple_light(),
+        Some("cornell_smoke") => cornell_smoke(),
+        Some("model") => {
+            mesh_model(&arg_value("--model").expect("The model scene needs a --model path."))
+        }
+        Some("final") | None => final_scene(),
+        Some(other) => panic!("Unknown scene: {}", other),
+    };
+
+    let renderer = Renderer::new(opts);
+
+    // Stop early and keep the partial image once the optional time limit is reached
+    let time_limit = arg_value("--time-limit")
+        .map(|secs| Duration::from_secs_f64(secs.parse().expect("Invalid --time-limit value.")));
+    let cancel = CancellationToken::new();
+
+    // Optionally draw a live preview of the image above the progress bar
+    let mut preview = std::env::args().any(|arg| arg == "--preview").then(|| {
+        let columns = arg_value("--preview-width")
+            .or_else(|| std::env::var("COLUMNS").ok())
+            .and_then(|columns| columns.parse().ok())
+            .unwrap_or(80);
+        TerminalPreview::new(columns)
+    });
+
+    let progress = |progress: &RenderProgress| {
+        if let Some(preview) = preview.as_mut() {
+            preview.update(progress.framebuffer).expect("Failed to draw the render preview.");
+        }
+        draw_progress_bar(progress);
+        if time_limit.is_some_and(|limit| progress.elapsed >= limit) {
+            cancel.cancel();
         }
+    };
+
+    let fb = renderer.render_with_progress(&world, progress, &cancel);
+    if let Some(preview) = preview.as_mut() {
+        preview.draw(&fb).expect("Failed to draw the render preview.");
+    }
+    eprintln!();
+    if cancel.is_cancelled() {
+        eprintln!("Render stopped at the time limit; writing the partial image.");
     }
+    fb.print_ppm();
 
+    report_stats();
     eprintln!("Done.");
 }
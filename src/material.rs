@@ -100,19 +100,33 @@ impl From<Color> for Lambertian {
 
 impl Scatter for Lambertian {
     fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<(Color, Ray)> {
-        let mut scatter_direction = rec.normal + Vec3::random_in_unit_sphere().normalize();
-        if scatter_direction.near_zero() {
-            // Catch degenerate scatter direction (equal to normal)
-            scatter_direction = rec.normal;
+        // Sample a cosine-weighted direction about the shading normal in the shading frame
+        let mut local_direction = Vec3::Z + Vec3::random_in_unit_sphere().normalize();
+        if local_direction.near_zero() {
+            // Catch degenerate scatter direction (opposite to the random vector)
+            local_direction = Vec3::Z;
         }
+        let scatter_direction = keep_outside(rec.shading_to_world(local_direction), rec);
 
-        let scattered = Ray::new(rec.point, scatter_direction, r_in.time());
-        let attenuation = self.albedo.color_value(rec.point, rec.u, rec.v);
+        let scattered = rec.spawn_ray(scatter_direction, r_in.time());
+        let attenuation = self.albedo.hit_color(rec);
 
         Some((attenuation, scattered))
     }
 }
 
+/// Keep a scattered direction on the side of the geometric surface that the ray arrived from,
+/// mirroring it back if a bent shading normal would otherwise send it through the surface.
+fn keep_outside(direction: Vec3, rec: &HitRecord) -> Vec3 {
+    let n = rec.geometric_normal;
+    let below = direction.dot(n);
+    if below < 0.0 {
+        direction - 2.0 * below * n
+    } else {
+        direction
+    }
+}
+
 /// A material with reflective metal scattering.
 #[derive(Clone, Copy, Debug)]
 pub struct Metal {
@@ -130,9 +144,10 @@ impl Scatter for Metal {
     fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<(Color, Ray)> {
         let reflected = r_in.direction().reflect(rec.normal).normalize();
         let scattered =
-            Ray::new(rec.point, reflected + self.fuzz * Vec3::random_in_unit_sphere(), r_in.time());
+            rec.spawn_ray(reflected + self.fuzz * Vec3::random_in_unit_sphere(), r_in.time());
 
-        if scattered.direction().dot(rec.normal) > 0.0 {
+        // Reflections about a bent shading normal can point into the surface, which would leak light
+        if scattered.direction().dot(rec.geometric_normal) > 0.0 {
             Some((self.albedo, scattered))
         } else {
             None
@@ -171,14 +186,30 @@ impl Scatter for Dielectric {
         let mut rng = thread_rng();
         let cannot_refract = refraction_ratio * sin_theta > 1.0;
         let will_reflect = rng.gen::<f64>() < Self::reflectance(cos_theta, refraction_ratio);
-
-        let direction = if cannot_refract || will_reflect {
-            unit_direction.reflect(rec.normal)
-        } else {
-            unit_direction.refract(rec.normal, refraction_ratio)
+        let reflect = cannot_refract || will_reflect;
+
+        let bend = |normal: Vec3| {
+            if reflect {
+                unit_direction.reflect(normal)
+            } else {
+                unit_direction.refract(normal, refraction_ratio)
+            }
         };
 
-        let scattered = Ray::new(rec.point, direction, r_in.time());
+        // Reflections must stay on the incoming side of the geometric surface and refractions
+        // must cross it, which a bent shading normal can break, so fall back to the true surface
+        let mut direction = bend(rec.normal);
+        if (direction.dot(rec.geometric_normal) > 0.0) != reflect {
+            let cos_theta = ((-1.0) * unit_direction).dot(rec.geometric_normal).min(1.0);
+            let sin_theta = (1.0 - cos_theta.powi(2)).sqrt();
+            direction = if reflect || refraction_ratio * sin_theta > 1.0 {
+                unit_direction.reflect(rec.geometric_normal)
+            } else {
+                bend(rec.geometric_normal)
+            };
+        }
+
+        let scattered = rec.spawn_ray(direction, r_in.time());
 
         Some((Color::new(1.0, 1.0, 1.0), scattered))
     }
@@ -232,8 +263,8 @@ impl From<Color> for Isotropic {
 
 impl Scatter for Isotropic {
     fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<(Color, Ray)> {
-        let scatered = Ray::new(rec.point, Vec3::random_in_unit_sphere(), r_in.time());
-        let attenuation = self.albedo.color_value(rec.point, rec.u, rec.v);
+        let scatered = rec.spawn_ray(Vec3::random_in_unit_sphere(), r_in.time());
+        let attenuation = self.albedo.hit_color(rec);
         Some((attenuation, scatered))
     }
 }
@@ -8,6 +8,7 @@ use crate::{
     ray::Ray,
 };
 
+/// A volume of constant density that scatters rays inside a boundary object.
 #[derive(Clone, Debug)]
 pub struct ConstantMedium {
     boundary: Box<Hittable>,
@@ -29,8 +30,8 @@ impl ConstantMedium {
 impl Hit for ConstantMedium {
     fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<HitRecord> {
         let rec1 = self.boundary.hit(r, f64::NEG_INFINITY, f64::INFINITY);
-        let rec1_s = rec1.as_ref().map(|rec| rec.s).unwrap_or(f64::NEG_INFINITY);
-        let rec2 = self.boundary.hit(r, rec1_s + 0.0001, f64::INFINITY);
+        let rec2_min = rec1.as_ref().map(|rec| rec.s_beyond(r)).unwrap_or(f64::NEG_INFINITY);
+        let rec2 = self.boundary.hit(r, rec2_min, f64::INFINITY);
 
         if rec1.is_none() || rec2.is_none() {
             return None;
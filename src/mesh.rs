@@ -0,0 +1,360 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{
+    aabb::AABB,
+    bvh::IndexBVH,
+    color::Color,
+    hit::{Hit, HitRecord},
+    material::Material,
+    ray::Ray,
+    triangle::{intersect_triangle, padded_bounding_box, set_triangle_frame},
+    vec::Vec3,
+};
+
+/// Indices of the vertex attributes used by one triangle of a mesh.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MeshTriangle {
+    pub positions: [u32; 3],
+    pub normals: Option<[u32; 3]>,
+    pub uvs: Option<[u32; 3]>,
+}
+
+/// A named range of triangles within a mesh.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MeshGroup {
+    pub name: String,
+    pub triangles: Range<usize>,
+}
+
+/// Vertex buffers of a deforming mesh sampled at one point in time.
+///
+/// The positions are indexed like `MeshData::positions`, and the normals,
+/// which may be left empty to keep the mesh's own, like `MeshData::normals`.
+/// Each buffer holds as many entries as the mesh's own.
+///
+/// ```
+/// use raytracing_rust::{
+///     color::Color,
+///     hit::Hit,
+///     material::Lambertian,
+///     mesh::{MeshData, MeshKey, MeshTriangle, TriangleMesh},
+///     ray::Ray,
+///     vec::Vec3,
+/// };
+///
+/// // A triangle whose top vertex swings from X = 0 to X = 2 over the shutter
+/// let mut data = MeshData::new();
+/// data.triangles = vec![MeshTriangle { positions: [0, 1, 2], normals: None, uvs: None }];
+/// data.positions =
+///     vec![Vec3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0), Vec3::new(0.0, 2.0, 0.0)];
+/// for (time, top) in [(0.0, Vec3::new(0.0, 2.0, 0.0)), (1.0, Vec3::new(2.0, 2.0, 0.0))] {
+///     let positions = vec![data.positions[0], data.positions[1], top];
+///     data.keys.push(MeshKey { time, positions, normals: Vec::new() });
+/// }
+///
+/// let mesh = TriangleMesh::new(data, Lambertian::from(Color::new(0.5, 0.5, 0.5)));
+/// assert!(mesh.bounding_box(0.0, 1.0).unwrap().max().x >= 2.0);
+///
+/// let at = |time| Ray::new(Vec3::new(0.2, 1.5, 1.0), Vec3::new(0.0, 0.0, -1.0), time);
+/// assert!(mesh.hit(&at(0.0), 0.001, f64::INFINITY).is_some());
+/// assert!(mesh.hit(&at(1.0), 0.001, f64::INFINITY).is_none());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MeshKey {
+    pub time: f64,
+    pub positions: Vec<Vec3>,
+    pub normals: Vec<Vec3>,
+}
+
+/// Vertex attribute buffers and the index buffer of a triangle mesh.
+///
+/// Vertex colors are optional, and when present are indexed like the positions.
+/// A deforming mesh also carries keys of vertex buffers in increasing order of time,
+/// which replace the positions and are interpolated at the time of each ray, holding
+/// the first and last keys outside of their range.
+#[derive(Clone, Debug, Default)]
+pub struct MeshData {
+    pub positions: Vec<Vec3>,
+    pub colors: Vec<Color>,
+    pub normals: Vec<Vec3>,
+    pub uvs: Vec<(f64, f64)>,
+    pub triangles: Vec<MeshTriangle>,
+    pub groups: Vec<MeshGroup>,
+    pub keys: Vec<MeshKey>,
+}
+
+/// The pair of mesh keys surrounding a point in time and the weight of the later one.
+#[derive(Clone, Copy, Debug)]
+struct KeyBlend {
+    keys: (usize, usize),
+    weight: f64,
+}
+
+impl MeshData {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Get the positions of the vertices of triangle `idx`.
+    pub fn triangle_positions(&self, idx: usize) -> [Vec3; 3] {
+        self.triangles[idx].positions.map(|i| self.positions[i as usize])
+    }
+
+    /// Get the positions of the vertices of triangle `idx` at `time`, for deforming meshes.
+    pub fn triangle_positions_at(&self, idx: usize, time: f64) -> [Vec3; 3] {
+        self.blended_positions(idx, self.key_blend(time))
+    }
+
+    /// Find the keys to interpolate between at `time`, if the mesh deforms.
+    fn key_blend(&self, time: f64) -> Option<KeyBlend> {
+        if self.keys.is_empty() {
+            return None;
+        }
+
+        let idx = self.keys.partition_point(|key| key.time <= time);
+        let blend = if idx == 0 {
+            KeyBlend { keys: (0, 0), weight: 0.0 }
+        } else if idx == self.keys.len() {
+            KeyBlend { keys: (idx - 1, idx - 1), weight: 0.0 }
+        } else {
+            let (t0, t1) = (self.keys[idx - 1].time, self.keys[idx].time);
+            KeyBlend { keys: (idx - 1, idx), weight: (time - t0) / (t1 - t0) }
+        };
+        Some(blend)
+    }
+
+    fn blended_positions(&self, idx: usize, blend: Option<KeyBlend>) -> [Vec3; 3] {
+        let indices = self.triangles[idx].positions;
+        match blend {
+            Some(KeyBlend { keys: (k0, k1), weight }) => indices.map(|i| {
+                let (p0, p1) =
+                    (self.keys[k0].positions[i as usize], self.keys[k1].positions[i as usize]);
+                p0 + weight * (p1 - p0)
+            }),
+            None => indices.map(|i| self.positions[i as usize]),
+        }
+    }
+
+    fn blended_normals(&self, indices: [u32; 3], blend: Option<KeyBlend>) -> [Vec3; 3] {
+        match blend {
+            Some(KeyBlend { keys: (k0, k1), weight }) if !self.keys[k0].normals.is_empty() => {
+                indices.map(|i| {
+                    let (n0, n1) =
+                        (self.keys[k0].normals[i as usize], self.keys[k1].normals[i as usize]);
+                    n0 + weight * (n1 - n0)
+                })
+            }
+            _ => indices.map(|i| self.normals[i as usize]),
+        }
+    }
+
+    /// Check that the keys can be blended with each other and looked up by time,
+    /// which needs strictly increasing times and buffers the size of the mesh's own.
+    fn check_keys(&self) {
+        for (idx, key) in self.keys.iter().enumerate() {
+            assert_eq!(
+                key.positions.len(),
+                self.positions.len(),
+                "Mesh key {} has {} positions, but the mesh has {}.",
+                idx,
+                key.positions.len(),
+                self.positions.len()
+            );
+            assert_eq!(
+                key.normals.is_empty(),
+                self.keys[0].normals.is_empty(),
+                "Mesh keys must either all have normals or all leave them out, \
+                 but key {} differs from key 0.",
+                idx
+            );
+            assert!(
+                key.normals.is_empty() || key.normals.len() == self.normals.len(),
+                "Mesh key {} has {} normals, but the mesh has {}.",
+                idx,
+                key.normals.len(),
+                self.normals.len()
+            );
+            if idx > 0 {
+                assert!(
+                    self.keys[idx - 1].time < key.time,
+                    "Mesh key times must be strictly increasing, but key {} is at {} after {}.",
+                    idx,
+                    key.time,
+                    self.keys[idx - 1].time
+                );
+            }
+        }
+    }
+
+    /// Get a bounding box around triangle `idx` over its whole motion.
+    fn triangle_bounding_box(&self, idx: usize) -> AABB {
+        if self.keys.is_empty() {
+            return padded_bounding_box(&self.triangle_positions(idx));
+        }
+
+        let indices = self.triangles[idx].positions;
+        let points: Vec<Vec3> =
+            self.keys.iter().flat_map(|key| indices.map(|i| key.positions[i as usize])).collect();
+        padded_bounding_box(&points)
+    }
+
+    /// Look up a group of triangles by name.
+    pub fn group(&self, name: &str) -> Option<&MeshGroup> {
+        self.groups.iter().find(|group| group.name == name)
+    }
+}
+
+/// A triangle mesh whose vertex data is shared between all of its triangles.
+///
+/// The mesh keeps its own BVH over the triangles, so it can be placed in a scene
+/// as a single hittable object regardless of its triangle count.
+///
+/// ```
+/// use raytracing_rust::{
+///     color::Color,
+///     hit::Hit,
+///     material::Lambertian,
+///     mesh::{MeshData, MeshTriangle, TriangleMesh},
+///     ray::Ray,
+///     vec::Vec3,
+/// };
+///
+/// // A unit square in the XY plane made of two triangles
+/// let mut data = MeshData::new();
+/// data.positions = vec![
+///     Vec3::new(0.0, 0.0, 0.0),
+///     Vec3::new(1.0, 0.0, 0.0),
+///     Vec3::new(1.0, 1.0, 0.0),
+///     Vec3::new(0.0, 1.0, 0.0),
+/// ];
+/// data.triangles = vec![
+///     MeshTriangle { positions: [0, 1, 2], normals: None, uvs: None },
+///     MeshTriangle { positions: [0, 2, 3], normals: None, uvs: None },
+/// ];
+///
+/// let mesh = TriangleMesh::new(data, Lambertian::from(Color::new(0.5, 0.5, 0.5)));
+/// let r = Ray::new(Vec3::new(0.25, 0.75, 1.0), Vec3::new(0.0, 0.0, -1.0), 0.0);
+/// assert!(mesh.hit(&r, 0.001, f64::INFINITY).is_some());
+/// ```
+#[derive(Clone, Debug)]
+pub struct TriangleMesh {
+    data: Arc<MeshData>,
+    bvh: Arc<IndexBVH>,
+    /// Bounding boxes of the mesh at each of its keys, if it deforms.
+    key_boxes: Arc<Vec<AABB>>,
+    material: Material,
+}
+
+impl TriangleMesh {
+    /// Create a mesh from all of the triangles in `data`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the keys of `data` are inconsistent, as described for `from_range`.
+    pub fn new(data: impl Into<Arc<MeshData>>, material: impl Into<Material>) -> Self {
+        let data = data.into();
+        let n = data.triangles.len();
+        Self::from_range(data, 0..n, material)
+    }
+
+    /// Create a mesh from the triangles of the named group in `data`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the keys of `data` are inconsistent, as described for `from_range`.
+    pub fn from_group(
+        data: impl Into<Arc<MeshData>>,
+        name: &str,
+        material: impl Into<Material>,
+    ) -> Option<Self> {
+        let data = data.into();
+        let range = data.group(name)?.triangles.clone();
+        Some(Self::from_range(data, range, material))
+    }
+
+    /// Create a mesh from a range of the triangles in `data`,
+    /// sharing the vertex buffers with any other mesh built from the same data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the times of the keys of `data` are not strictly increasing,
+    /// if some keys have normals and others do not, or if any key has a different
+    /// number of positions or normals than `data` itself.
+    pub fn from_range(
+        data: impl Into<Arc<MeshData>>,
+        triangles: Range<usize>,
+        material: impl Into<Material>,
+    ) -> Self {
+        let data = data.into();
+        data.check_keys();
+
+        // Triangles of a deforming mesh are bounded over all of their keys
+        let key_boxes = data
+            .keys
+            .iter()
+            .map(|key| {
+                let indices = data.triangles[triangles.clone()].iter().flat_map(|t| t.positions);
+                padded_bounding_box(&indices.map(|i| key.positions[i as usize]).collect::<Vec<_>>())
+            })
+            .collect();
+        let bvh = IndexBVH::new(triangles.collect(), |idx| data.triangle_bounding_box(idx));
+
+        Self { data, bvh: Arc::new(bvh), key_boxes: Arc::new(key_boxes), material: material.into() }
+    }
+
+    pub fn data(&self) -> &Arc<MeshData> {
+        &self.data
+    }
+}
+
+impl Hit for TriangleMesh {
+    fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<HitRecord> {
+        let blend = self.data.key_blend(r.time());
+        let (s, (idx, b1, b2)) = self.bvh.hit(r, s_min, s_max, |idx, s_min, s_max| {
+            intersect_triangle(r, self.data.blended_positions(idx, blend), s_min, s_max)
+                .map(|(s, b1, b2)| (s, (idx, b1, b2)))
+        })?;
+
+        let b0 = 1.0 - b1 - b2;
+        let triangle = &self.data.triangles[idx];
+
+        let (u, v) = match triangle.uvs {
+            Some(uvs) => {
+                let [uv0, uv1, uv2] = uvs.map(|i| self.data.uvs[i as usize]);
+                (b0 * uv0.0 + b1 * uv1.0 + b2 * uv2.0, b0 * uv0.1 + b1 * uv1.1 + b2 * uv2.1)
+            }
+            None => (b1, b2),
+        };
+
+        let mut rec = HitRecord::new(s, u, v, r.at(s), self.material.clone());
+        set_triangle_frame(
+            &mut rec,
+            r,
+            self.data.blended_positions(idx, blend),
+            triangle.normals.map(|normals| self.data.blended_normals(normals, blend)),
+            triangle.uvs.map(|uvs| uvs.map(|i| self.data.uvs[i as usize])),
+            (b1, b2),
+        );
+
+        if !self.data.colors.is_empty() {
+            let [c0, c1, c2] = triangle.positions.map(|i| self.data.colors[i as usize]);
+            rec.vertex_color = Some(b0 * c0 + b1 * c1 + b2 * c2);
+        }
+
+        Some(rec)
+    }
+
+    fn bounding_box(&self, time0: f64, time1: f64) -> Option<AABB> {
+        let bbox = self.bvh.bounding_box()?;
+        if self.key_boxes.is_empty() {
+            return Some(bbox);
+        }
+
+        // Interpolated positions lie between their keys, so the keys from the one
+        // at or before `time0` to the one at or after `time1` bound the interval
+        let keys = &self.data.keys;
+        let first = keys.partition_point(|key| key.time <= time0).saturating_sub(1);
+        let last = keys.partition_point(|key| key.time < time1).min(keys.len() - 1);
+        self.key_boxes[first..=last.max(first)].iter().copied().reduce(AABB::surrounding_box)
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{
+    aabb::AABB,
+    animation::{Keyframes, Lerp},
+    hit::{Hit, HitRecord},
+    hittable::Hittable,
+    ray::Ray,
+    transform::AffineMap,
+    vec::{Affine3, Quat, Vec3},
+};
+
+/// A placement of an object by a scale, then a rotation, then a translation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pose {
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
+}
+
+impl Pose {
+    pub const IDENTITY: Self =
+        Self { translation: Vec3::ZERO, rotation: Quat::IDENTITY, scale: Vec3::ONE };
+
+    pub fn new(translation: Vec3, rotation: Quat, scale: Vec3) -> Self {
+        Self { translation, rotation, scale }
+    }
+
+    pub fn from_translation(translation: Vec3) -> Self {
+        Self { translation, ..Self::IDENTITY }
+    }
+
+    pub fn with_translation(mut self, translation: Vec3) -> Self {
+        self.translation = translation;
+        self
+    }
+
+    pub fn with_rotation(mut self, rotation: Quat) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: Vec3) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn to_affine(&self) -> Affine3 {
+        Affine3::from_scale_rotation_translation(self.scale, self.rotation, self.translation)
+    }
+}
+
+impl Default for Pose {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Lerp for Pose {
+    fn lerp(a: &Self, b: &Self, t: f64) -> Self {
+        Self {
+            translation: Lerp::lerp(&a.translation, &b.translation, t),
+            rotation: Lerp::lerp(&a.rotation, &b.rotation, t),
+            scale: Lerp::lerp(&a.scale, &b.scale, t),
+        }
+    }
+}
+
+/// An object moving through keyframed poses, blurred by the time of each ray.
+///
+/// Translation and scale are interpolated linearly between keys and rotation
+/// along the shorter arc, so any hittable, including a whole BVH, can move
+/// during the shutter interval.
+///
+/// ```
+/// use raytracing_rust::{
+///     animation::Keyframes, color::Color, cuboid::Cuboid, hit::Hit, material::Lambertian,
+///     motion::{Motion, Pose}, ray::Ray, vec::Vec3,
+/// };
+///
+/// // A unit cube sliding four units along X over the shutter
+/// let cube = Cuboid::new(Vec3::ZERO, Vec3::ONE, Lambertian::from(Color::new(0.5, 0.5, 0.5)));
+/// let poses = Keyframes::new(Pose::IDENTITY)
+///     .with_key(1.0, Pose::from_translation(Vec3::new(4.0, 0.0, 0.0)));
+/// let sliding = Motion::new(cube, poses);
+///
+/// let bbox = sliding.bounding_box(0.0, 1.0).unwrap();
+/// assert!(bbox.min().x <= 0.0 && bbox.max().x >= 5.0);
+///
+/// let early = Ray::new(Vec3::new(0.5, 0.5, 5.0), Vec3::new(0.0, 0.0, -1.0), 0.0);
+/// let late = Ray::new(Vec3::new(0.5, 0.5, 5.0), Vec3::new(0.0, 0.0, -1.0), 1.0);
+/// assert!(sliding.hit(&early, 0.001, f64::INFINITY).is_some());
+/// assert!(sliding.hit(&late, 0.001, f64::INFINITY).is_none());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Motion {
+    object: Box<Hittable>,
+    poses: Keyframes<Pose>,
+}
+
+impl Motion {
+    /// Number of poses sampled between keys when bounding the motion.
+    const BOUND_SAMPLES: usize = 16;
+
+    pub fn new(object: impl Into<Hittable>, poses: Keyframes<Pose>) -> Self {
+        Self { object: Box::new(object.into()), poses }
+    }
+
+    pub fn poses(&self) -> &Keyframes<Pose> {
+        &self.poses
+    }
+}
+
+impl Hit for Motion {
+    fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<HitRecord> {
+        let map = AffineMap::new(self.poses.value_at(r.time()).to_affine());
+        let rec = self.object.hit(&map.to_object(r), s_min, s_max)?;
+        Some(map.to_world(rec))
+    }
+
+    fn bounding_box(&self, time0: f64, time1: f64) -> Option<AABB> {
+        let corners = self.object.bounding_box(time0, time1)?.corners();
+        let corners_at = |t: f64| {
+            let transform = self.poses.value_at(t).to_affine();
+            corners.map(|p| transform.transform_point3(p))
+        };
+
+        // Sample the motion at every key within the interval and evenly between them
+        let mut times = vec![time0];
+        times.extend(self.poses.key_times().filter(|t| *t > time0 && *t < time1));
+        times.push(time1);
+
+        let mut samples = Vec::new();
+        for pair in times.windows(2) {
+            for k in 0..Self::BOUND_SAMPLES {
+                let t = pair[0] + (pair[1] - pair[0]) * k as f64 / Self::BOUND_SAMPLES as f64;
+                samples.push(corners_at(t));
+            }
+        }
+        samples.push(corners_at(time1));
+
+        // Corners can swing off the straight line between samples, but by less
+        // than the distance they move from one sample to the next, so pad by that
+        let mut padding: f64 = 0.0;
+        for pair in samples.windows(2) {
+            for (p0, p1) in pair[0].iter().zip(&pair[1]) {
+                padding = padding.max(p0.distance(*p1));
+            }
+        }
+
+        let motion_box = AABB::from_points(samples.into_iter().flatten());
+        let padding = Vec3::splat(padding);
+        Some(AABB::new(motion_box.min() - padding, motion_box.max() + padding))
+    }
+}
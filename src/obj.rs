@@ -0,0 +1,194 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    ops::Range,
+    path::Path,
+};
+
+use crate::{
+    mesh::{MeshData, MeshGroup, MeshTriangle},
+    vec::Vec3,
+};
+
+/// Load the geometry of a Wavefront OBJ file.
+///
+/// Supports vertex positions, normals and texture coordinates, faces with any number
+/// of vertices, and `g`/`o` groups. Polygons are triangulated as a fan around their first
+/// vertex, which assumes that they are convex. Groups named more than once are merged,
+/// with their triangles moved together. Material libraries are ignored.
+pub fn load_obj(path: impl AsRef<Path>) -> io::Result<MeshData> {
+    let file = File::open(path)?;
+    parse_obj(BufReader::new(file))
+}
+
+/// Parse Wavefront OBJ geometry from a reader.
+///
+/// ```
+/// use raytracing_rust::obj::parse_obj;
+///
+/// let source = "
+/// v 0 0 0
+/// v 1 0 0
+/// v 1 1 0
+/// v 0 1 0
+/// g square
+/// f 1 2 3 4
+/// ";
+///
+/// let data = parse_obj(source.as_bytes()).unwrap();
+/// assert_eq!(data.triangles.len(), 2);
+/// assert_eq!(data.group("square").unwrap().triangles, 0..2);
+/// ```
+///
+/// A group reopened later in the file takes in the triangles of both of its parts:
+///
+/// ```
+/// use raytracing_rust::obj::parse_obj;
+///
+/// let source = "
+/// v 0 0 0
+/// v 1 0 0
+/// v 1 1 0
+/// g a
+/// f 1 2 3
+/// g b
+/// f 1 3 2
+/// g a
+/// f 2 3 1
+/// ";
+///
+/// let data = parse_obj(source.as_bytes()).unwrap();
+/// assert_eq!(data.groups.len(), 2);
+/// assert_eq!(data.group("a").unwrap().triangles, 0..2);
+/// assert_eq!(data.group("b").unwrap().triangles, 2..3);
+/// assert_eq!(data.triangles[1].positions, [1, 2, 0]);
+/// ```
+pub fn parse_obj(reader: impl BufRead) -> io::Result<MeshData> {
+    let mut data = MeshData::new();
+    let mut group = MeshGroup { name: "default".to_string(), triangles: 0..0 };
+
+    for (line_idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        let invalid = |msg: &str| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", line_idx + 1, msg))
+        };
+
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("v") => {
+                data.positions.push(parse_vec3(tokens).ok_or_else(|| invalid("bad vertex"))?)
+            }
+            Some("vn") => {
+                data.normals.push(parse_vec3(tokens).ok_or_else(|| invalid("bad normal"))?)
+            }
+            Some("vt") => {
+                let u =
+                    parse_float(tokens.next()).ok_or_else(|| invalid("bad texture coordinate"))?;
+                let v = parse_float(tokens.next()).unwrap_or(0.0);
+                data.uvs.push((u, v));
+            }
+            Some("f") => {
+                let vertices = tokens
+                    .map(|token| parse_face_vertex(token, &data))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| invalid("bad face vertex"))?;
+                if vertices.len() < 3 {
+                    return Err(invalid("face has fewer than three vertices"));
+                }
+
+                for i in 1..vertices.len() - 1 {
+                    let corners = [vertices[0], vertices[i], vertices[i + 1]];
+                    data.triangles.push(MeshTriangle {
+                        positions: corners.map(|(p, _, _)| p),
+                        uvs: all_present(corners.map(|(_, t, _)| t)),
+                        normals: all_present(corners.map(|(_, _, n)| n)),
+                    });
+                }
+            }
+            Some("g") | Some("o") => {
+                let name = tokens.collect::<Vec<_>>().join(" ");
+                let start = data.triangles.len();
+                finish_group(&mut data, group, start);
+                group = MeshGroup { name, triangles: start..start };
+            }
+            _ => {}
+        }
+    }
+
+    let end = data.triangles.len();
+    finish_group(&mut data, group, end);
+    merge_groups(&mut data);
+
+    Ok(data)
+}
+
+fn finish_group(data: &mut MeshData, mut group: MeshGroup, end: usize) {
+    group.triangles.end = end;
+    if !group.triangles.is_empty() {
+        data.groups.push(group);
+    }
+}
+
+/// Merge groups sharing a name into the first of them, reordering the triangles
+/// so that each group's are contiguous, in the order their groups first appear.
+fn merge_groups(data: &mut MeshData) {
+    let mut merged: Vec<(String, Vec<Range<usize>>)> = Vec::new();
+    for group in data.groups.drain(..) {
+        match merged.iter_mut().find(|(name, _)| *name == group.name) {
+            Some((_, ranges)) => ranges.push(group.triangles),
+            None => merged.push((group.name, vec![group.triangles])),
+        }
+    }
+
+    let mut triangles = Vec::with_capacity(data.triangles.len());
+    for (name, ranges) in merged {
+        let start = triangles.len();
+        for range in ranges {
+            triangles.extend_from_slice(&data.triangles[range]);
+        }
+        data.groups.push(MeshGroup { name, triangles: start..triangles.len() });
+    }
+    data.triangles = triangles;
+}
+
+fn parse_float(token: Option<&str>) -> Option<f64> {
+    token?.parse().ok()
+}
+
+fn parse_vec3<'a>(mut tokens: impl Iterator<Item = &'a str>) -> Option<Vec3> {
+    let x = parse_float(tokens.next())?;
+    let y = parse_float(tokens.next())?;
+    let z = parse_float(tokens.next())?;
+    Some(Vec3::new(x, y, z))
+}
+
+/// Resolve a one-based or negative (relative to the end) OBJ index into a buffer of length `len`.
+fn resolve_index(token: &str, len: usize) -> Option<u32> {
+    let idx: i64 = token.parse().ok()?;
+    let resolved = if idx < 0 { len as i64 + idx } else { idx - 1 };
+    (0..len as i64).contains(&resolved).then_some(resolved as u32)
+}
+
+/// Parse a face vertex of the form `p`, `p/t`, `p//n` or `p/t/n`.
+fn parse_face_vertex(token: &str, data: &MeshData) -> Option<(u32, Option<u32>, Option<u32>)> {
+    let mut parts = token.split('/');
+    let position = resolve_index(parts.next()?, data.positions.len())?;
+
+    let uv = match parts.next() {
+        Some("") | None => None,
+        Some(t) => Some(resolve_index(t, data.uvs.len())?),
+    };
+    let normal = match parts.next() {
+        Some("") | None => None,
+        Some(n) => Some(resolve_index(n, data.normals.len())?),
+    };
+
+    Some((position, uv, normal))
+}
+
+fn all_present(indices: [Option<u32>; 3]) -> Option<[u32; 3]> {
+    match indices {
+        [Some(a), Some(b), Some(c)] => Some([a, b, c]),
+        _ => None,
+    }
+}
@@ -0,0 +1,49 @@
+use crate::vec::Vec3;
+
+/// An orthonormal basis, used to work in the local frame of an oriented object.
+///
+/// ```
+/// use raytracing_rust::{onb::ONB, vec::Vec3};
+///
+/// let onb = ONB::from_w(Vec3::new(0.0, 2.0, 0.0));
+/// let a = Vec3::new(1.0, 2.0, 3.0);
+/// assert!((onb.to_world(onb.to_local(a)) - a).length() < 1e-12);
+/// assert!((onb.to_local(Vec3::Y) - Vec3::Z).length() < 1e-12);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ONB {
+    u: Vec3,
+    v: Vec3,
+    w: Vec3,
+}
+
+impl ONB {
+    /// Create a right-handed basis whose `w` axis points along `w`.
+    pub fn from_w(w: Vec3) -> Self {
+        let w = w.normalize();
+        let (u, v) = w.any_orthonormal_pair();
+        Self { u, v, w }
+    }
+
+    pub fn u(&self) -> Vec3 {
+        self.u
+    }
+
+    pub fn v(&self) -> Vec3 {
+        self.v
+    }
+
+    pub fn w(&self) -> Vec3 {
+        self.w
+    }
+
+    /// Express a world-space vector in the coordinates of the basis.
+    pub fn to_local(&self, a: Vec3) -> Vec3 {
+        Vec3::new(a.dot(self.u), a.dot(self.v), a.dot(self.w))
+    }
+
+    /// Convert coordinates in the basis back into a world-space vector.
+    pub fn to_world(&self, a: Vec3) -> Vec3 {
+        a.x * self.u + a.y * self.v + a.z * self.w
+    }
+}
@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use crate::{
+    aabb::AABB,
+    bvh::IndexBVH,
+    color::Color,
+    float,
+    hit::{Hit, HitRecord},
+    material::Material,
+    ray::Ray,
+    sphere::Sphere,
+    vec::Vec3,
+};
+
+/// Flat buffers of particle positions, radii and colors.
+///
+/// Radii are either one per particle or a single radius shared by all of them.
+/// Colors are optional, and when present are indexed like the positions.
+#[derive(Clone, Debug, Default)]
+pub struct ParticleData {
+    pub positions: Vec<Vec3>,
+    pub radii: Vec<f64>,
+    pub colors: Vec<Color>,
+}
+
+impl ParticleData {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Create particles of the same radius at each of the `positions`.
+    pub fn uniform(positions: Vec<Vec3>, radius: f64) -> Self {
+        Self { positions, radii: vec![radius], colors: Vec::new() }
+    }
+
+    pub fn with_colors(mut self, colors: Vec<Color>) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    pub fn radius(&self, idx: usize) -> f64 {
+        if self.radii.len() == 1 {
+            self.radii[0]
+        } else {
+            self.radii[idx]
+        }
+    }
+}
+
+/// A set of spherical particles sharing one material, such as the output of a simulation.
+///
+/// The particles are stored in flat buffers and kept in the set's own BVH,
+/// so millions of them cost little more than their positions. The texture coordinates
+/// are those of a `Sphere`, and any particle colors are reported as the hit's vertex color,
+/// to be shown with a `VertexColorTexture`.
+///
+/// ```
+/// use raytracing_rust::{
+///     color::Color,
+///     hit::Hit,
+///     material::Lambertian,
+///     particles::{ParticleData, ParticleSet},
+///     ray::Ray,
+///     texture::{SolidColor, VertexColorTexture},
+///     vec::Vec3,
+/// };
+///
+/// let positions = (0..10).map(|i| Vec3::new(i as f64, 0.0, 0.0)).collect();
+/// let colors = (0..10).map(|i| Color::new(i as f64 / 10.0, 0.0, 0.0)).collect();
+/// let data = ParticleData::uniform(positions, 0.25).with_colors(colors);
+///
+/// let texture = VertexColorTexture::new(SolidColor::new(Color::new(0.5, 0.5, 0.5)));
+/// let particles = ParticleSet::new(data, Lambertian::new(texture));
+///
+/// let r = Ray::new(Vec3::new(3.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0), 0.0);
+/// let rec = particles.hit(&r, 0.001, f64::INFINITY).unwrap();
+/// assert!((rec.point.z - 0.25).abs() < 1e-9);
+/// assert_eq!(rec.vertex_color, Some(Color::new(0.3, 0.0, 0.0)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ParticleSet {
+    data: Arc<ParticleData>,
+    bvh: Arc<IndexBVH>,
+    material: Material,
+}
+
+impl ParticleSet {
+    /// Create a set of all the particles in `data`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` has neither one radius nor one per particle,
+    /// or has colors but not one per particle.
+    pub fn new(data: impl Into<Arc<ParticleData>>, material: impl Into<Material>) -> Self {
+        let data = data.into();
+        assert!(
+            data.radii.len() == 1 || data.radii.len() == data.len(),
+            "Particles need one radius or one per particle, but {} were given for {} particles.",
+            data.radii.len(),
+            data.len()
+        );
+        assert!(
+            data.colors.is_empty() || data.colors.len() == data.len(),
+            "Particle colors must be left out or given per particle, \
+             but {} were given for {} particles.",
+            data.colors.len(),
+            data.len()
+        );
+        let bvh = IndexBVH::new((0..data.len()).collect(), |idx| {
+            let r = Vec3::splat(data.radius(idx));
+            AABB::new(data.positions[idx] - r, data.positions[idx] + r)
+        });
+        Self { data, bvh: Arc::new(bvh), material: material.into() }
+    }
+
+    pub fn data(&self) -> &Arc<ParticleData> {
+        &self.data
+    }
+}
+
+impl Hit for ParticleSet {
+    fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<HitRecord> {
+        let a = r.direction().length_squared();
+        let (s, idx) = self.bvh.hit(r, s_min, s_max, |idx, s_min, s_max| {
+            let oc = r.origin() - self.data.positions[idx];
+            let half_b = oc.dot(r.direction());
+            let c = oc.length_squared() - self.data.radius(idx).powi(2);
+
+            let discriminant = half_b * half_b - a * c;
+            if discriminant < 0.0 {
+                return None;
+            }
+
+            let sqrtd = discriminant.sqrt();
+            [(-half_b - sqrtd) / a, (-half_b + sqrtd) / a]
+                .into_iter()
+                .find(|s| (s_min..=s_max).contains(s))
+                .map(|s| (s, idx))
+        })?;
+
+        let (center, radius) = (self.data.positions[idx], self.data.radius(idx));
+        let (point, error) = float::sphere_point(center, radius, r.at(s));
+        let outward_normal = (point - center) / radius;
+        let (u, v) = Sphere::get_uv(outward_normal);
+
+        let mut rec = HitRecord::new(s, u, v, point, self.material.clone());
+        rec.error = error;
+        rec.set_face_normal(r, outward_normal);
+        rec.vertex_color = self.data.colors.get(idx).copied();
+
+        Some(rec)
+    }
+
+    fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<AABB> {
+        self.bvh.bounding_box()
+    }
+}
@@ -6,7 +6,7 @@ const POINT_COUNT: usize = 256;
 
 fn permute(p: &mut [usize], n: usize) {
     let mut rng = rand::thread_rng();
-    for i in (0..n as usize).rev() {
+    for i in (0..n).rev() {
         let target = rng.gen_range(0..i + 1);
         p.swap(i, target);
     }
@@ -31,6 +31,7 @@ fn interpolate(c: &[[[Vec3; 2]; 2]; 2], u: f64, v: f64, w: f64) -> f64 {
     accum
 }
 
+/// Generator for smoothly varying Perlin noise.
 #[derive(Clone, Debug)]
 pub struct Perlin {
     rand_vecs: Vec<Vec3>,
@@ -49,6 +50,7 @@ impl Perlin {
         }
     }
 
+    /// Get the turbulence at `point`, summing `depth` octaves of noise.
     pub fn turb(&self, point: Vec3, depth: usize) -> f64 {
         let mut accum = 0.0;
         let mut temp_point = point;
@@ -109,3 +111,9 @@ impl Perlin {
         p
     }
 }
+
+impl Default for Perlin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{
+    aabb::AABB,
+    float,
+    hit::{Hit, HitRecord},
+    material::Material,
+    onb::ONB,
+    ray::Ray,
+    vec::Vec3,
+};
+
+/// An infinite plane through a point, facing along its normal.
+///
+/// Planes have no bounding box, so a `BVH` keeps them outside of its tree.
+/// The texture coordinates repeat across the plane in square tiles,
+/// one world unit wide unless set `with_tile_size`.
+///
+/// ```
+/// use raytracing_rust::{color::Color, hit::Hit, material::Lambertian, plane::Plane, ray::Ray, vec::Vec3};
+///
+/// let plane = Plane::new(
+///     Vec3::new(0.0, -1.0, 0.0),
+///     Vec3::new(0.0, 1.0, 0.0),
+///     Lambertian::from(Color::new(0.5, 0.5, 0.5)),
+/// );
+///
+/// let r = Ray::new(Vec3::new(100.0, 1.0, -40.0), Vec3::new(1.0, -1.0, 0.0), 0.0);
+/// let rec = plane.hit(&r, 0.001, f64::INFINITY).unwrap();
+/// assert!((rec.point.x - 102.0).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Plane {
+    point: Vec3,
+    onb: ONB,
+    tile_size: f64,
+    material: Material,
+}
+
+impl Plane {
+    pub fn new(point: Vec3, normal: Vec3, material: impl Into<Material>) -> Self {
+        Self { point, onb: ONB::from_w(normal), tile_size: 1.0, material: material.into() }
+    }
+
+    pub fn with_tile_size(mut self, tile_size: f64) -> Self {
+        self.tile_size = tile_size;
+        self
+    }
+
+    pub fn point(&self) -> Vec3 {
+        self.point
+    }
+
+    pub fn normal(&self) -> Vec3 {
+        self.onb.w()
+    }
+}
+
+impl Hit for Plane {
+    fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<HitRecord> {
+        let normal = self.onb.w();
+        let denom = normal.dot(r.direction());
+
+        // The ray is parallel to the plane
+        if denom.abs() < 1e-12 {
+            return None;
+        }
+
+        let s = normal.dot(self.point - r.origin()) / denom;
+        if s < s_min || s > s_max {
+            return None;
+        }
+
+        let (point, error) = float::plane_point(self.point, normal, r.at(s));
+        let local = self.onb.to_local(point - self.point) / self.tile_size;
+        let u = local.x.rem_euclid(1.0);
+        let v = local.y.rem_euclid(1.0);
+
+        let mut rec = HitRecord::new(s, u, v, point, self.material.clone());
+        rec.error = error;
+        rec.set_face_normal(r, normal);
+
+        Some(rec)
+    }
+
+    fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<AABB> {
+        None
+    }
+}
@@ -0,0 +1,427 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use crate::{
+    color::Color,
+    mesh::{MeshData, MeshGroup, MeshTriangle},
+    vec::Vec3,
+};
+
+/// Load the geometry of a PLY file.
+///
+/// Supports the ASCII and both binary encodings. Vertices may carry positions (`x`, `y`, `z`),
+/// normals (`nx`, `ny`, `nz`), texture coordinates (`u`/`v` or `s`/`t`) and colors
+/// (`red`, `green`, `blue`), and faces are read from the `vertex_indices` list of the
+/// `face` element. Polygons are triangulated as a fan around their first vertex,
+/// and any other elements or properties are skipped.
+pub fn load_ply(path: impl AsRef<Path>) -> io::Result<MeshData> {
+    let file = File::open(path)?;
+    parse_ply(BufReader::new(file))
+}
+
+/// Parse PLY geometry from a reader.
+///
+/// ```
+/// use raytracing_rust::ply::parse_ply;
+///
+/// let source = "ply
+/// format ascii 1.0
+/// element vertex 4
+/// property float x
+/// property float y
+/// property float z
+/// property uchar red
+/// property uchar green
+/// property uchar blue
+/// element face 1
+/// property list uchar int vertex_indices
+/// end_header
+/// 0 0 0 255 0 0
+/// 1 0 0 0 255 0
+/// 1 1 0 0 0 255
+/// 0 1 0 255 255 255
+/// 4 0 1 2 3
+/// ";
+///
+/// let data = parse_ply(source.as_bytes()).unwrap();
+/// assert_eq!(data.triangles.len(), 2);
+/// assert_eq!(data.colors[0].x, 1.0);
+/// ```
+pub fn parse_ply(mut reader: impl BufRead) -> io::Result<MeshData> {
+    let header = Header::parse(&mut reader)?;
+
+    let mut data = MeshData::new();
+    let mut body = match header.format {
+        Format::Ascii => {
+            Body::Ascii(AsciiReader { reader, tokens: Vec::new(), line: header.lines })
+        }
+        Format::BinaryLittleEndian => Body::Binary(reader, false),
+        Format::BinaryBigEndian => Body::Binary(reader, true),
+    };
+
+    for element in &header.elements {
+        match element.name.as_str() {
+            "vertex" => read_vertices(&mut body, element, &mut data)?,
+            "face" => read_faces(&mut body, element, &mut data)?,
+            _ => {
+                for _ in 0..element.count {
+                    for property in &element.properties {
+                        body.skip_property(property)?;
+                    }
+                }
+            }
+        }
+    }
+
+    let n = data.triangles.len();
+    if n > 0 {
+        data.groups.push(MeshGroup { name: "default".to_string(), triangles: 0..n });
+    }
+
+    Ok(data)
+}
+
+fn invalid(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ScalarType {
+    Int8,
+    UInt8,
+    Int16,
+    UInt16,
+    Int32,
+    UInt32,
+    Float32,
+    Float64,
+}
+
+impl ScalarType {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "char" | "int8" => Some(Self::Int8),
+            "uchar" | "uint8" => Some(Self::UInt8),
+            "short" | "int16" => Some(Self::Int16),
+            "ushort" | "uint16" => Some(Self::UInt16),
+            "int" | "int32" => Some(Self::Int32),
+            "uint" | "uint32" => Some(Self::UInt32),
+            "float" | "float32" => Some(Self::Float32),
+            "double" | "float64" => Some(Self::Float64),
+            _ => None,
+        }
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Self::Int8 | Self::UInt8 => 1,
+            Self::Int16 | Self::UInt16 => 2,
+            Self::Int32 | Self::UInt32 | Self::Float32 => 4,
+            Self::Float64 => 8,
+        }
+    }
+
+    /// Scale for converting a color channel of this type into the range `[0, 1]`.
+    fn color_scale(self) -> f64 {
+        match self {
+            Self::Int8 | Self::UInt8 => 1.0 / 255.0,
+            Self::Int16 | Self::UInt16 => 1.0 / 65535.0,
+            Self::Int32 | Self::UInt32 => 1.0 / 4294967295.0,
+            Self::Float32 | Self::Float64 => 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum PropertyKind {
+    Scalar(ScalarType),
+    List { count: ScalarType, item: ScalarType },
+}
+
+#[derive(Clone, Debug)]
+struct Property {
+    name: String,
+    kind: PropertyKind,
+}
+
+#[derive(Clone, Debug)]
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+impl Element {
+    fn property_index(&self, names: &[&str]) -> Option<usize> {
+        self.properties.iter().position(|p| names.contains(&p.name.as_str()))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Header {
+    format: Format,
+    elements: Vec<Element>,
+    /// Number of lines read up to and including `end_header`.
+    lines: usize,
+}
+
+impl Header {
+    fn parse(reader: &mut impl BufRead) -> io::Result<Self> {
+        let mut format = None;
+        let mut elements: Vec<Element> = Vec::new();
+        let mut lines = 0;
+
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 {
+                return Err(invalid("missing end_header"));
+            }
+            lines += 1;
+            let invalid_line = |msg: &str| invalid(format!("line {}: {}", lines, msg));
+
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("ply") if lines == 1 => {}
+                _ if lines == 1 => return Err(invalid_line("not a PLY file")),
+                Some("format") => {
+                    format = Some(match tokens.next() {
+                        Some("ascii") => Format::Ascii,
+                        Some("binary_little_endian") => Format::BinaryLittleEndian,
+                        Some("binary_big_endian") => Format::BinaryBigEndian,
+                        _ => return Err(invalid_line("unknown format")),
+                    })
+                }
+                Some("element") => {
+                    let name = tokens.next().ok_or_else(|| invalid_line("bad element"))?;
+                    let count = tokens
+                        .next()
+                        .and_then(|t| t.parse().ok())
+                        .ok_or_else(|| invalid_line("bad element count"))?;
+                    elements.push(Element {
+                        name: name.to_string(),
+                        count,
+                        properties: Vec::new(),
+                    });
+                }
+                Some("property") => {
+                    let element = elements
+                        .last_mut()
+                        .ok_or_else(|| invalid_line("property before element"))?;
+                    let scalar = |token: Option<&str>| {
+                        token.and_then(ScalarType::parse).ok_or_else(|| invalid_line("bad type"))
+                    };
+
+                    let kind = match tokens.next() {
+                        Some("list") => {
+                            let count = scalar(tokens.next())?;
+                            let item = scalar(tokens.next())?;
+                            PropertyKind::List { count, item }
+                        }
+                        token => PropertyKind::Scalar(scalar(token)?),
+                    };
+                    let name = tokens.next().ok_or_else(|| invalid_line("bad property"))?;
+                    element.properties.push(Property { name: name.to_string(), kind });
+                }
+                Some("end_header") => break,
+                _ => {}
+            }
+        }
+
+        let format = format.ok_or_else(|| invalid("missing format"))?;
+        Ok(Self { format, elements, lines })
+    }
+}
+
+/// Reader for whitespace-separated values of an ASCII body, one element per line.
+struct AsciiReader<R> {
+    reader: R,
+    tokens: Vec<String>,
+    line: usize,
+}
+
+impl<R: BufRead> AsciiReader<R> {
+    fn next_token(&mut self) -> io::Result<String> {
+        while self.tokens.is_empty() {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Err(invalid("unexpected end of file"));
+            }
+            self.line += 1;
+            self.tokens = line.split_whitespace().rev().map(str::to_string).collect();
+        }
+        Ok(self.tokens.pop().unwrap())
+    }
+
+    fn read_value(&mut self) -> io::Result<f64> {
+        let token = self.next_token()?;
+        token.parse().map_err(|_| invalid(format!("line {}: bad value {:?}", self.line, token)))
+    }
+}
+
+enum Body<R> {
+    Ascii(AsciiReader<R>),
+    /// A binary body, and whether it is big-endian.
+    Binary(R, bool),
+}
+
+impl<R: BufRead> Body<R> {
+    fn read_scalar(&mut self, ty: ScalarType) -> io::Result<f64> {
+        match self {
+            Self::Ascii(ascii) => ascii.read_value(),
+            Self::Binary(reader, big_endian) => {
+                let mut buf = [0u8; 8];
+                let bytes = &mut buf[..ty.size()];
+                reader.read_exact(bytes).map_err(|err| match err.kind() {
+                    io::ErrorKind::UnexpectedEof => invalid("unexpected end of file"),
+                    _ => err,
+                })?;
+                if !*big_endian {
+                    bytes.reverse();
+                }
+
+                // Bytes are now in big-endian order
+                Ok(match ty {
+                    ScalarType::Int8 => bytes[0] as i8 as f64,
+                    ScalarType::UInt8 => bytes[0] as f64,
+                    ScalarType::Int16 => i16::from_be_bytes([bytes[0], bytes[1]]) as f64,
+                    ScalarType::UInt16 => u16::from_be_bytes([bytes[0], bytes[1]]) as f64,
+                    ScalarType::Int32 => i32::from_be_bytes(buf[..4].try_into().unwrap()) as f64,
+                    ScalarType::UInt32 => u32::from_be_bytes(buf[..4].try_into().unwrap()) as f64,
+                    ScalarType::Float32 => f32::from_be_bytes(buf[..4].try_into().unwrap()) as f64,
+                    ScalarType::Float64 => f64::from_be_bytes(buf),
+                })
+            }
+        }
+    }
+
+    fn read_list(&mut self, count: ScalarType, item: ScalarType) -> io::Result<Vec<f64>> {
+        let n = self.read_scalar(count)?;
+        if n < 0.0 {
+            return Err(invalid("negative list length"));
+        }
+        (0..n as usize).map(|_| self.read_scalar(item)).collect()
+    }
+
+    fn skip_property(&mut self, property: &Property) -> io::Result<()> {
+        match property.kind {
+            PropertyKind::Scalar(ty) => self.read_scalar(ty).map(|_| ()),
+            PropertyKind::List { count, item } => self.read_list(count, item).map(|_| ()),
+        }
+    }
+}
+
+fn read_vertices<R: BufRead>(
+    body: &mut Body<R>,
+    element: &Element,
+    data: &mut MeshData,
+) -> io::Result<()> {
+    let find = |names: &[&str]| element.property_index(names);
+    let position = [find(&["x"]), find(&["y"]), find(&["z"])];
+    let normal = [find(&["nx"]), find(&["ny"]), find(&["nz"])];
+    let uv =
+        [find(&["u", "s", "texture_u", "texture_s"]), find(&["v", "t", "texture_v", "texture_t"])];
+    let color = [find(&["red"]), find(&["green"]), find(&["blue"])];
+
+    let [Some(x), Some(y), Some(z)] = position else {
+        return Err(invalid("vertex element without x, y and z properties"));
+    };
+    let normal = match normal {
+        [Some(nx), Some(ny), Some(nz)] => Some([nx, ny, nz]),
+        _ => None,
+    };
+    let uv = match uv {
+        [Some(u), Some(v)] => Some([u, v]),
+        _ => None,
+    };
+    let color = match color {
+        [Some(r), Some(g), Some(b)] => Some([r, g, b]),
+        _ => None,
+    };
+
+    let mut values = vec![0.0; element.properties.len()];
+    for _ in 0..element.count {
+        for (value, property) in values.iter_mut().zip(&element.properties) {
+            match property.kind {
+                PropertyKind::Scalar(ty) => *value = body.read_scalar(ty)?,
+                PropertyKind::List { .. } => body.skip_property(property)?,
+            }
+        }
+
+        data.positions.push(Vec3::new(values[x], values[y], values[z]));
+        if let Some([nx, ny, nz]) = normal {
+            data.normals.push(Vec3::new(values[nx], values[ny], values[nz]));
+        }
+        if let Some([u, v]) = uv {
+            data.uvs.push((values[u], values[v]));
+        }
+        if let Some(channels) = color {
+            let [r, g, b] = channels.map(|i| match element.properties[i].kind {
+                PropertyKind::Scalar(ty) => values[i] * ty.color_scale(),
+                PropertyKind::List { .. } => 0.0,
+            });
+            data.colors.push(Color::new(r, g, b));
+        }
+    }
+
+    Ok(())
+}
+
+fn read_faces<R: BufRead>(
+    body: &mut Body<R>,
+    element: &Element,
+    data: &mut MeshData,
+) -> io::Result<()> {
+    let indices_property = element
+        .property_index(&["vertex_indices", "vertex_index"])
+        .ok_or_else(|| invalid("face element without vertex_indices property"))?;
+
+    // Normals and texture coordinates are per vertex, so they share the position indices
+    let has_normals = !data.normals.is_empty();
+    let has_uvs = !data.uvs.is_empty();
+    let n_vertices = data.positions.len();
+
+    for face_idx in 0..element.count {
+        let mut indices = Vec::new();
+        for (i, property) in element.properties.iter().enumerate() {
+            match property.kind {
+                PropertyKind::List { count, item } if i == indices_property => {
+                    indices = body.read_list(count, item)?;
+                }
+                _ => body.skip_property(property)?,
+            }
+        }
+
+        let indices = indices
+            .into_iter()
+            .map(|idx| {
+                (idx >= 0.0 && (idx as usize) < n_vertices).then_some(idx as u32).ok_or_else(|| {
+                    invalid(format!("face {}: vertex index {} out of range", face_idx, idx))
+                })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        if indices.len() < 3 {
+            return Err(invalid(format!("face {}: fewer than three vertices", face_idx)));
+        }
+
+        for i in 1..indices.len() - 1 {
+            let corners = [indices[0], indices[i], indices[i + 1]];
+            data.triangles.push(MeshTriangle {
+                positions: corners,
+                normals: has_normals.then_some(corners),
+                uvs: has_uvs.then_some(corners),
+            });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use std::{
+    fmt::Write as _,
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    color::{Color, ColorFormat},
+    render::Framebuffer,
+};
+
+/// A downsampled view of a framebuffer drawn in the terminal with ANSI truecolor escapes.
+///
+/// Each character cell is an upper half block whose foreground and background colors
+/// show two preview pixels stacked vertically, which keeps the preview pixels roughly square.
+/// Colors go through the same averaging and gamma correction as the final image.
+#[derive(Clone, Debug)]
+pub struct TerminalPreview {
+    columns: usize,
+    interval: Duration,
+    last_draw: Option<Instant>,
+    lines_drawn: usize,
+}
+
+impl TerminalPreview {
+    /// Create a preview that is `columns` characters wide.
+    pub fn new(columns: usize) -> Self {
+        Self { columns, interval: Duration::from_millis(500), last_draw: None, lines_drawn: 0 }
+    }
+
+    /// Set the minimum time between two redraws by `update`.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Redraw the preview if at least the refresh interval has passed since the last draw.
+    pub fn update(&mut self, fb: &Framebuffer) -> io::Result<()> {
+        match self.last_draw {
+            Some(last) if last.elapsed() < self.interval => Ok(()),
+            _ => self.draw(fb),
+        }
+    }
+
+    /// Draw the preview to stderr, replacing the previously drawn preview.
+    ///
+    /// The cursor is left at the start of the line below the preview.
+    pub fn draw(&mut self, fb: &Framebuffer) -> io::Result<()> {
+        let out = self.render_string(fb);
+
+        let mut stderr = io::stderr().lock();
+        stderr.write_all(out.as_bytes())?;
+        stderr.flush()?;
+
+        self.last_draw = Some(Instant::now());
+        Ok(())
+    }
+
+    fn render_string(&mut self, fb: &Framebuffer) -> String {
+        let width = self.columns.min(fb.width()).max(1);
+        let height = ((width * fb.height()) as f64 / fb.width() as f64).round().max(1.0) as usize;
+
+        let mut out = String::new();
+        if self.lines_drawn > 0 {
+            // Move back up over the previous preview to draw on top of it
+            write!(out, "\r\x1b[{}A", self.lines_drawn).unwrap();
+        }
+
+        for row in (0..height).step_by(2) {
+            for col in 0..width {
+                let [tr, tg, tb] = Self::preview_pixel(fb, col, row, width, height);
+                write!(out, "\x1b[38;2;{};{};{}m", tr, tg, tb).unwrap();
+
+                if row + 1 < height {
+                    let [br, bg, bb] = Self::preview_pixel(fb, col, row + 1, width, height);
+                    write!(out, "\x1b[48;2;{};{};{}m", br, bg, bb).unwrap();
+                } else {
+                    out.push_str("\x1b[49m");
+                }
+                out.push('▀');
+            }
+            out.push_str("\x1b[0m\n");
+        }
+
+        self.lines_drawn = height.div_ceil(2);
+        out
+    }
+
+    /// Average the framebuffer pixels covered by preview pixel `(col, row)`.
+    fn preview_pixel(
+        fb: &Framebuffer,
+        col: usize,
+        row: usize,
+        width: usize,
+        height: usize,
+    ) -> [u8; 3] {
+        let x0 = col * fb.width() / width;
+        let x1 = ((col + 1) * fb.width() / width).max(x0 + 1);
+        let y0 = row * fb.height() / height;
+        let y1 = ((row + 1) * fb.height() / height).max(y0 + 1);
+
+        let mut sum = Color::new(0.0, 0.0, 0.0);
+        let mut count = 0;
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let samples = fb.samples(x, y);
+                if samples > 0 {
+                    sum += fb.accumulated(x, y) / samples as f64;
+                    count += 1;
+                }
+            }
+        }
+
+        sum.to_rgb8(count.max(1))
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{
+    aabb::AABB,
+    float,
+    hit::{Hit, HitRecord},
+    material::Material,
+    ray::Ray,
+    triangle::padded_bounding_box,
+    vec::Vec3,
+};
+
+/// A parallelogram spanned by two edge vectors from a corner point.
+///
+/// The texture coordinates `(u, v)` run from 0 to 1 along the first and second edges,
+/// and the outward normal points along the cross product of the first edge with the second.
+///
+/// ```
+/// use raytracing_rust::{color::Color, hit::Hit, material::Lambertian, quad::Quad, ray::Ray, vec::Vec3};
+///
+/// let quad = Quad::new(
+///     Vec3::new(0.0, 0.0, 0.0),
+///     Vec3::new(2.0, 0.0, 0.0),
+///     Vec3::new(0.0, 1.0, 1.0),
+///     Lambertian::from(Color::new(0.5, 0.5, 0.5)),
+/// );
+///
+/// let r = Ray::new(Vec3::new(0.5, 5.0, 0.5), Vec3::new(0.0, -1.0, 0.0), 0.0);
+/// let rec = quad.hit(&r, 0.001, f64::INFINITY).unwrap();
+/// assert!((rec.u - 0.25).abs() < 1e-9 && (rec.v - 0.5).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Quad {
+    corner: Vec3,
+    edge1: Vec3,
+    edge2: Vec3,
+    normal: Vec3,
+    // Offset of the plane of the quad along its normal
+    d: f64,
+    // Scaled normal for projecting hit points onto the edges
+    w: Vec3,
+    material: Material,
+}
+
+impl Quad {
+    pub fn new(corner: Vec3, edge1: Vec3, edge2: Vec3, material: impl Into<Material>) -> Self {
+        let n = edge1.cross(edge2);
+        let normal = n.normalize();
+        let d = normal.dot(corner);
+        let w = n / n.dot(n);
+        Self { corner, edge1, edge2, normal, d, w, material: material.into() }
+    }
+
+    pub fn corner(&self) -> Vec3 {
+        self.corner
+    }
+
+    pub fn edges(&self) -> [Vec3; 2] {
+        [self.edge1, self.edge2]
+    }
+
+    pub fn normal(&self) -> Vec3 {
+        self.normal
+    }
+}
+
+impl Hit for Quad {
+    fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<HitRecord> {
+        let denom = self.normal.dot(r.direction());
+
+        // The ray is parallel to the plane of the quad
+        if denom.abs() < 1e-12 {
+            return None;
+        }
+
+        let s = (self.d - self.normal.dot(r.origin())) / denom;
+        if s < s_min || s > s_max {
+            return None;
+        }
+
+        // Express the hit point in the coordinates of the two edges
+        let (point, error) = float::plane_point(self.corner, self.normal, r.at(s));
+        let planar = point - self.corner;
+        let u = self.w.dot(planar.cross(self.edge2));
+        let v = self.w.dot(self.edge1.cross(planar));
+        if !(0.0..=1.0).contains(&u) || !(0.0..=1.0).contains(&v) {
+            return None;
+        }
+
+        let mut rec = HitRecord::new(s, u, v, point, self.material.clone());
+        rec.error = error;
+        rec.set_face_normal(r, self.normal);
+
+        Some(rec)
+    }
+
+    fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<AABB> {
+        let c = self.corner;
+        Some(padded_bounding_box(&[c, c + self.edge1, c + self.edge2, c + self.edge1 + self.edge2]))
+    }
+}
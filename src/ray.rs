@@ -1,5 +1,13 @@
 use crate::vec::Vec3;
 
+/// A ray with an origin, a direction of travel and a time of origination.
+///
+/// ```
+/// use raytracing_rust::{ray::Ray, vec::Vec3};
+///
+/// let r = Ray::new(Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 2.0, 0.0), 0.0);
+/// assert_eq!(r.at(0.5), Vec3::new(1.0, 1.0, 0.0));
+/// ```
 #[derive(Clone, Copy, Debug)]
 pub struct Ray {
     origin: Vec3,
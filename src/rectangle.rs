@@ -6,6 +6,7 @@ use crate::{
     vec::Vec3,
 };
 
+/// A rectangle in the plane `z = k`.
 #[derive(Clone, Debug)]
 pub struct XYRectangle {
     x0: f64,
@@ -37,7 +38,8 @@ impl Hit for XYRectangle {
 
         let u = (x - self.x0) / (self.x1 - self.x0);
         let v = (y - self.y0) / (self.y1 - self.y0);
-        let point = r.at(s);
+        // Put the point exactly in the plane, leaving no error off the surface
+        let point = Vec3::new(x, y, self.k);
         let outward_normal = Vec3::new(0.0, 0.0, 1.0);
 
         let mut rec = HitRecord::new(s, u, v, point, self.material.clone());
@@ -47,14 +49,13 @@ impl Hit for XYRectangle {
     }
 
     fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<AABB> {
-        // The bounding box must have a non-zero width in each dimension,
-        // so pad the bounding box a little bit
-        let min = Vec3::new(self.x0, self.y0, self.k - 1e-3 * self.k);
-        let max = Vec3::new(self.x1, self.y1, self.k + 1e-3 * self.k);
+        let min = Vec3::new(self.x0, self.y0, self.k);
+        let max = Vec3::new(self.x1, self.y1, self.k);
         Some(AABB::new(min, max))
     }
 }
 
+/// A rectangle in the plane `y = k`.
 #[derive(Clone, Debug)]
 pub struct XZRectangle {
     x0: f64,
@@ -86,7 +87,8 @@ impl Hit for XZRectangle {
 
         let u = (x - self.x0) / (self.x1 - self.x0);
         let v = (z - self.z0) / (self.z1 - self.z0);
-        let point = r.at(s);
+        // Put the point exactly in the plane, leaving no error off the surface
+        let point = Vec3::new(x, self.k, z);
         let outward_normal = Vec3::new(0.0, 1.0, 0.0);
 
         let mut rec = HitRecord::new(s, u, v, point, self.material.clone());
@@ -96,14 +98,13 @@ impl Hit for XZRectangle {
     }
 
     fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<AABB> {
-        // The bounding box must have a non-zero width in each dimension,
-        // so pad the bounding box a little bit
-        let min = Vec3::new(self.x0, self.k - 1e-3 * self.k, self.z0);
-        let max = Vec3::new(self.x1, self.k + 1e-3 * self.k, self.z1);
+        let min = Vec3::new(self.x0, self.k, self.z0);
+        let max = Vec3::new(self.x1, self.k, self.z1);
         Some(AABB::new(min, max))
     }
 }
 
+/// A rectangle in the plane `x = k`.
 #[derive(Clone, Debug)]
 pub struct YZRectangle {
     y0: f64,
@@ -135,7 +136,8 @@ impl Hit for YZRectangle {
 
         let u = (y - self.y0) / (self.y1 - self.y0);
         let v = (z - self.z0) / (self.z1 - self.z0);
-        let point = r.at(s);
+        // Put the point exactly in the plane, leaving no error off the surface
+        let point = Vec3::new(self.k, y, z);
         let outward_normal = Vec3::new(1.0, 0.0, 0.0);
 
         let mut rec = HitRecord::new(s, u, v, point, self.material.clone());
@@ -145,10 +147,8 @@ impl Hit for YZRectangle {
     }
 
     fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<AABB> {
-        // The bounding box must have a non-zero width in each dimension,
-        // so pad the bounding box a little bit
-        let min = Vec3::new(self.k - 1e-3 * self.k, self.y0, self.z0);
-        let max = Vec3::new(self.k + 1e-3 * self.k, self.y1, self.z1);
+        let min = Vec3::new(self.k, self.y0, self.z0);
+        let max = Vec3::new(self.k, self.y1, self.z1);
         Some(AABB::new(min, max))
     }
 }
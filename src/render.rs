@@ -0,0 +1,271 @@
+use std::{
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use image::{ImageResult, RgbImage};
+use rand::Rng;
+use rayon::{
+    iter::{IndexedParallelIterator, ParallelIterator},
+    slice::ParallelSliceMut,
+};
+
+use crate::{
+    camera::{Camera, ViewOptions},
+    color::{ray_color, Color, ColorFormat},
+    hittable::HittableList,
+    stats::{self, Counter},
+};
+
+/// Accumulated pixel colors and sample counts for an image.
+///
+/// Pixels are stored in row-major order starting from the top-left corner of the image.
+///
+/// ```
+/// use raytracing_rust::render::Framebuffer;
+///
+/// let fb = Framebuffer::new(4, 2);
+/// assert_eq!(fb.samples(3, 1), 0);
+/// assert_eq!(fb.rgb8(3, 1), [0, 0, 0]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Framebuffer {
+    width: usize,
+    height: usize,
+    accum: Vec<Color>,
+    samples: Vec<u64>,
+}
+
+impl Framebuffer {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            accum: vec![Color::new(0.0, 0.0, 0.0); width * height],
+            samples: vec![0; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the sum of all samples taken for pixel `(x, y)`.
+    pub fn accumulated(&self, x: usize, y: usize) -> Color {
+        self.accum[y * self.width + x]
+    }
+
+    /// Get the number of samples taken for pixel `(x, y)`.
+    pub fn samples(&self, x: usize, y: usize) -> u64 {
+        self.samples[y * self.width + x]
+    }
+
+    /// Get the gamma-corrected 8-bit color of pixel `(x, y)`.
+    pub fn rgb8(&self, x: usize, y: usize) -> [u8; 3] {
+        let idx = y * self.width + x;
+        self.accum[idx].to_rgb8(self.samples[idx].max(1))
+    }
+
+    /// Write the image as a plain-text PPM to stdout.
+    pub fn print_ppm(&self) {
+        println!("P3");
+        println!("{} {}", self.width, self.height);
+        println!("255");
+
+        for (pixel_color, &samples) in self.accum.iter().zip(&self.samples) {
+            println!("{}", pixel_color.format_color(samples.max(1)));
+        }
+    }
+
+    /// Save the image to a file, with the format determined from the extension of `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> ImageResult<()> {
+        let mut data = Vec::with_capacity(3 * self.width * self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                data.extend(self.rgb8(x, y));
+            }
+        }
+
+        let img = RgbImage::from_raw(self.width as u32, self.height as u32, data)
+            .expect("Pixel buffer does not match the image dimensions.");
+
+        img.save(path)
+    }
+}
+
+/// A flag that can be shared with a running render to request that it stops early.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Progress of a running render, reported after each sampling pass.
+#[derive(Clone, Copy, Debug)]
+pub struct RenderProgress<'a> {
+    /// Fraction of the total samples taken so far, between zero and one.
+    pub fraction: f64,
+    pub elapsed: Duration,
+    /// Estimated time until the render finishes, when enough samples have been taken to tell.
+    pub eta: Option<Duration>,
+    pub samples_per_sec: f64,
+    /// The image accumulated so far.
+    pub framebuffer: &'a Framebuffer,
+}
+
+/// Renders a world into a framebuffer, using a camera built from the view options.
+///
+/// ```
+/// use raytracing_rust::{
+///     camera::ViewOptions, color::Color, hittable::HittableList, material::Lambertian,
+///     render::Renderer, sphere::Sphere, vec::Vec3,
+/// };
+///
+/// let mut world = HittableList::new();
+/// world.push(Sphere::stationary(Vec3::ZERO, 1.0, Lambertian::from(Color::new(0.5, 0.5, 0.5))));
+///
+/// let opts = ViewOptions::new().with_image_width(16).with_samples_per_pixel(4);
+/// let fb = Renderer::new(opts).render(&world);
+///
+/// assert_eq!((fb.width(), fb.height()), (16, 9));
+/// assert_eq!(fb.samples(0, 0), 4);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Renderer {
+    opts: ViewOptions,
+    cam: Camera,
+}
+
+impl Renderer {
+    pub fn new(opts: ViewOptions) -> Self {
+        let cam = Camera::from_options(&opts);
+        Self { opts, cam }
+    }
+
+    pub fn options(&self) -> &ViewOptions {
+        &self.opts
+    }
+
+    pub fn camera(&self) -> &Camera {
+        &self.cam
+    }
+
+    /// Render the world into a new framebuffer.
+    pub fn render(&self, world: &HittableList) -> Framebuffer {
+        self.render_with_progress(world, |_| {}, &CancellationToken::new())
+    }
+
+    /// Render the world, reporting progress to a callback and stopping early when cancelled.
+    ///
+    /// The image is refined in passes of `samples_per_pass` samples per pixel,
+    /// and `progress` is called once after every pass. The token is checked before each
+    /// scanline of a pass, so a cancelled render returns promptly with the samples taken so far.
+    ///
+    /// ```
+    /// use raytracing_rust::{
+    ///     camera::ViewOptions, hittable::HittableList,
+    ///     render::{CancellationToken, Renderer},
+    /// };
+    ///
+    /// let opts = ViewOptions::new().with_image_width(16).with_samples_per_pixel(8).with_samples_per_pass(2);
+    /// let cancel = CancellationToken::new();
+    ///
+    /// // Stop after the first pass, which leaves a partial image with two samples per pixel
+    /// let fb = Renderer::new(opts).render_with_progress(&HittableList::new(), |_| cancel.cancel(), &cancel);
+    /// assert_eq!(fb.samples(0, 0), 2);
+    /// ```
+    pub fn render_with_progress<F>(
+        &self,
+        world: &HittableList,
+        mut progress: F,
+        cancel: &CancellationToken,
+    ) -> Framebuffer
+    where
+        F: FnMut(&RenderProgress),
+    {
+        let opts = &self.opts;
+        let start = Instant::now();
+        let width = opts.image_width as usize;
+        let height = opts.image_height as usize;
+        let mut fb = Framebuffer::new(width, height);
+
+        let total_samples = (width * height) as u64 * opts.samples_per_pixel;
+        let mut samples_taken = 0;
+        let mut pixel_samples = 0;
+
+        while pixel_samples < opts.samples_per_pixel && !cancel.is_cancelled() {
+            let pass_samples =
+                opts.samples_per_pass.max(1).min(opts.samples_per_pixel - pixel_samples);
+
+            let rows_done: u64 = fb
+                .accum
+                .par_chunks_mut(width)
+                .zip(fb.samples.par_chunks_mut(width))
+                .enumerate()
+                .map(|(y, (accum_row, samples_row))| {
+                    if cancel.is_cancelled() {
+                        return 0;
+                    }
+
+                    // Rows are stored from the top of the image, but v runs from the bottom
+                    let j = height - 1 - y;
+                    for (i, (pixel_color, samples)) in
+                        accum_row.iter_mut().zip(samples_row).enumerate()
+                    {
+                        for _ in 0..pass_samples {
+                            let mut rng = rand::thread_rng();
+                            let random_u: f64 = rng.gen();
+                            let random_v: f64 = rng.gen();
+
+                            let u = ((i as f64) + random_u) / ((width - 1) as f64);
+                            let v = ((j as f64) + random_v) / ((height - 1) as f64);
+
+                            let r = self.cam.get_ray(u, v);
+                            stats::increment(Counter::PrimaryRays);
+                            *pixel_color += ray_color(&r, world, opts.background, opts.max_depth);
+                        }
+                        *samples += pass_samples;
+                    }
+
+                    1
+                })
+                .sum();
+
+            pixel_samples += pass_samples;
+            samples_taken += rows_done * width as u64 * pass_samples;
+
+            let elapsed = start.elapsed();
+            let fraction = samples_taken as f64 / total_samples as f64;
+            let samples_per_sec = samples_taken as f64 / elapsed.as_secs_f64();
+            let eta = (samples_per_sec > 0.0).then(|| {
+                Duration::from_secs_f64((total_samples - samples_taken) as f64 / samples_per_sec)
+            });
+
+            progress(&RenderProgress { fraction, elapsed, eta, samples_per_sec, framebuffer: &fb });
+        }
+
+        stats::record_time(Counter::RenderNanos, start);
+        fb
+    }
+}
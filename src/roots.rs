@@ -0,0 +1,141 @@
+use std::ops::Range;
+
+/// Find the real roots of `a s^2 + 2 half_b s + c = 0` in increasing order.
+///
+/// A vanishing leading coefficient falls back to the single root of the linear equation,
+/// which is returned twice.
+pub fn solve_quadratic(a: f64, half_b: f64, c: f64) -> Option<(f64, f64)> {
+    if a.abs() < 1e-12 {
+        if half_b == 0.0 {
+            return None;
+        }
+        let s = -c / (2.0 * half_b);
+        return Some((s, s));
+    }
+
+    let discriminant = half_b * half_b - a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    // Avoid cancellation by computing the larger-magnitude root first
+    let q = -(half_b + half_b.signum() * discriminant.sqrt());
+    if q == 0.0 {
+        return Some((0.0, 0.0));
+    }
+    let (s0, s1) = (q / a, c / q);
+    Some(if s0 < s1 { (s0, s1) } else { (s1, s0) })
+}
+
+/// Find the real roots of `a s^4 + b s^3 + c s^2 + d s + e = 0` within `interval`,
+/// in increasing order.
+///
+/// ```
+/// use raytracing_rust::roots::solve_quartic;
+///
+/// // (s - 1)(s - 2)(s - 3)(s - 4)
+/// let roots = solve_quartic([1.0, -10.0, 35.0, -50.0, 24.0], 0.0..10.0);
+/// assert_eq!(roots.len(), 4);
+/// assert!(roots.iter().zip([1.0, 2.0, 3.0, 4.0]).all(|(s, t)| (s - t).abs() < 1e-9));
+/// ```
+pub fn solve_quartic(coeffs: [f64; 5], interval: Range<f64>) -> Vec<f64> {
+    polynomial_roots(&coeffs, interval)
+}
+
+/// Find the real roots of a polynomial within `interval`, in increasing order.
+///
+/// The coefficients are given from the highest degree down to the constant term.
+/// Roots are isolated between the extrema of the polynomial, which are found recursively
+/// as the roots of its derivative, and each monotonic piece that changes sign is then
+/// refined by safeguarded Newton iteration. Roots of even multiplicity, where the polynomial
+/// touches zero without crossing it, are only found if it evaluates to exactly zero there.
+pub fn polynomial_roots(coeffs: &[f64], interval: Range<f64>) -> Vec<f64> {
+    // Drop vanishing leading coefficients, so the degree is the true degree
+    let first = coeffs.iter().position(|c| *c != 0.0).unwrap_or(coeffs.len());
+    let coeffs = &coeffs[first..];
+    let (lo, hi) = (interval.start, interval.end);
+
+    match coeffs.len() {
+        0 | 1 => Vec::new(),
+        2 => {
+            let s = -coeffs[1] / coeffs[0];
+            if (lo..=hi).contains(&s) {
+                vec![s]
+            } else {
+                Vec::new()
+            }
+        }
+        n => {
+            // Split the interval at the extrema, between which the polynomial is monotonic
+            let derivative: Vec<f64> =
+                coeffs[..n - 1].iter().enumerate().map(|(i, c)| c * (n - 1 - i) as f64).collect();
+
+            let mut bounds = vec![lo];
+            bounds.extend(polynomial_roots(&derivative, interval));
+            bounds.push(hi);
+
+            let mut roots = Vec::new();
+            for pair in bounds.windows(2) {
+                if let Some(s) = monotonic_root(coeffs, &derivative, pair[0], pair[1]) {
+                    if roots.last().is_none_or(|last| s > *last) {
+                        roots.push(s);
+                    }
+                }
+            }
+            roots
+        }
+    }
+}
+
+fn evaluate(coeffs: &[f64], s: f64) -> f64 {
+    coeffs.iter().fold(0.0, |acc, c| acc * s + c)
+}
+
+/// Find the root of a polynomial that is monotonic on `[lo, hi]`, if it changes sign there.
+fn monotonic_root(coeffs: &[f64], derivative: &[f64], mut lo: f64, mut hi: f64) -> Option<f64> {
+    const MAX_ITERATIONS: usize = 100;
+
+    let f_lo = evaluate(coeffs, lo);
+    let f_hi = evaluate(coeffs, hi);
+    if f_lo == 0.0 {
+        return Some(lo);
+    }
+    if f_hi == 0.0 {
+        return Some(hi);
+    }
+    if f_lo.signum() == f_hi.signum() {
+        return None;
+    }
+
+    // Keep the bracket oriented so that the polynomial is negative at `lo`
+    if f_lo > 0.0 {
+        std::mem::swap(&mut lo, &mut hi);
+    }
+
+    let mut s = 0.5 * (lo + hi);
+    for _ in 0..MAX_ITERATIONS {
+        let f = evaluate(coeffs, s);
+        if f == 0.0 {
+            break;
+        }
+        if f < 0.0 {
+            lo = s;
+        } else {
+            hi = s;
+        }
+
+        // Take a Newton step, falling back to bisection if it leaves the bracket
+        let df = evaluate(derivative, s);
+        let newton = s - f / df;
+        let next =
+            if df != 0.0 && (newton - lo) * (newton - hi) < 0.0 { newton } else { 0.5 * (lo + hi) };
+
+        if (next - s).abs() <= 1e-14 * s.abs().max(1.0) {
+            s = next;
+            break;
+        }
+        s = next;
+    }
+
+    Some(s)
+}
@@ -0,0 +1,245 @@
+use std::{fmt, sync::Arc};
+
+use crate::{
+    bvh::BVH,
+    hittable::{Hittable, HittableList},
+    material::Material,
+    transform::Transform,
+    vec::Affine3,
+};
+
+/// Geometry built with the material a node inherits from its ancestors.
+type Shape = Arc<dyn Fn(Material) -> Hittable + Send + Sync>;
+
+/// What a node holds besides its children.
+#[derive(Clone)]
+enum Content {
+    Group,
+    Object(Box<Hittable>),
+    Shape(Shape),
+}
+
+impl fmt::Debug for Content {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Group => f.write_str("Group"),
+            Self::Object(object) => f.debug_tuple("Object").field(object).finish(),
+            Self::Shape(_) => f.write_str("Shape"),
+        }
+    }
+}
+
+/// A named node of a scene graph, with a local transform, a default material,
+/// a visibility flag and any number of children.
+///
+/// Children are placed by their own transform followed by their parent's, inherit
+/// the nearest default material set above them, and are hidden along with their parent.
+/// Objects keep their own materials, while shapes are built with the inherited one.
+/// Nodes can be looked up by name and changed before the graph is flattened for rendering.
+///
+/// Flattening dissolves groups without a transform into their parent and puts each
+/// transformed group under a single `Transform` of a `BVH` of its contents,
+/// so a group of many objects costs one transform per ray rather than one per object.
+///
+/// ```
+/// use raytracing_rust::{
+///     color::Color, hit::Hit, hittable::Hittable, material::{Lambertian, Material}, ray::Ray,
+///     scene::SceneNode, sphere::Sphere, vec::{Affine3, Vec3},
+/// };
+///
+/// let ball = |material: Material| Hittable::from(Sphere::stationary(Vec3::ZERO, 1.0, material));
+/// let mut scene = SceneNode::group("scene")
+///     .with_material(Lambertian::from(Color::new(0.5, 0.5, 0.5)))
+///     .with_child(
+///         SceneNode::group("balls")
+///             .with_transform(Affine3::from_translation(Vec3::new(0.0, 1.0, 0.0)))
+///             .with_child(SceneNode::shape("left", ball))
+///             .with_child(
+///                 SceneNode::shape("right", ball)
+///                     .with_transform(Affine3::from_translation(Vec3::new(3.0, 0.0, 0.0))),
+///             ),
+///     );
+///
+/// // Move the right ball from a script before rendering
+/// let right = scene.find_mut("right").unwrap();
+/// right.set_transform(Affine3::from_translation(Vec3::new(5.0, 0.0, 0.0)));
+///
+/// let world = scene.flatten(0.0, 1.0);
+/// let r = Ray::new(Vec3::new(5.0, 1.0, 10.0), Vec3::new(0.0, 0.0, -1.0), 0.0);
+/// let rec = world.hit(&r, 0.0, f64::INFINITY).unwrap();
+/// assert!((rec.point.z - 1.0).abs() < 1e-9);
+///
+/// scene.find_mut("balls").unwrap().set_visible(false);
+/// assert!(scene.flatten(0.0, 1.0).is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct SceneNode {
+    name: String,
+    content: Content,
+    transform: Affine3,
+    material: Option<Material>,
+    visible: bool,
+    children: Vec<SceneNode>,
+}
+
+impl SceneNode {
+    fn new(name: impl Into<String>, content: Content) -> Self {
+        Self {
+            name: name.into(),
+            content,
+            transform: Affine3::IDENTITY,
+            material: None,
+            visible: true,
+            children: Vec::new(),
+        }
+    }
+
+    /// Create a node holding nothing but its children.
+    pub fn group(name: impl Into<String>) -> Self {
+        Self::new(name, Content::Group)
+    }
+
+    /// Create a node holding an object with its own material.
+    pub fn object(name: impl Into<String>, object: impl Into<Hittable>) -> Self {
+        Self::new(name, Content::Object(Box::new(object.into())))
+    }
+
+    /// Create a node holding geometry built with the material it inherits.
+    pub fn shape(
+        name: impl Into<String>,
+        build: impl Fn(Material) -> Hittable + Send + Sync + 'static,
+    ) -> Self {
+        Self::new(name, Content::Shape(Arc::new(build)))
+    }
+
+    pub fn with_transform(mut self, transform: Affine3) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Set the material inherited by shapes in this node and below it,
+    /// unless a descendant sets its own.
+    pub fn with_material(mut self, material: impl Into<Material>) -> Self {
+        self.material = Some(material.into());
+        self
+    }
+
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    pub fn with_child(mut self, child: SceneNode) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the transform from this node's space into its parent's.
+    pub fn transform(&self) -> &Affine3 {
+        &self.transform
+    }
+
+    pub fn material(&self) -> Option<&Material> {
+        self.material.as_ref()
+    }
+
+    pub fn visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn children(&self) -> &[SceneNode] {
+        &self.children
+    }
+
+    pub fn set_transform(&mut self, transform: Affine3) {
+        self.transform = transform;
+    }
+
+    pub fn set_material(&mut self, material: impl Into<Material>) {
+        self.material = Some(material.into());
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    pub fn add_child(&mut self, child: SceneNode) {
+        self.children.push(child);
+    }
+
+    /// Find the first node with the given name, searching depth first from this node.
+    pub fn find(&self, name: &str) -> Option<&SceneNode> {
+        if self.name == name {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| child.find(name))
+    }
+
+    /// Find the first node with the given name for changing it, searching depth first from this node.
+    pub fn find_mut(&mut self, name: &str) -> Option<&mut SceneNode> {
+        if self.name == name {
+            return Some(self);
+        }
+        self.children.iter_mut().find_map(|child| child.find_mut(name))
+    }
+
+    /// Build the visible part of the graph into a list of hittables for rendering
+    /// between time `time0` and `time1`, which is empty if nothing is visible.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a visible shape has no material to inherit.
+    pub fn flatten(&self, time0: f64, time1: f64) -> HittableList {
+        let mut objects = Vec::new();
+        self.collect(None, &mut objects, time0, time1);
+        HittableList::from_vec(Self::combine(objects, time0, time1).into_iter().collect())
+    }
+
+    /// Add the hittables of the visible part of this node to its parent's `objects`.
+    fn collect(
+        &self,
+        inherited: Option<&Material>,
+        objects: &mut Vec<Hittable>,
+        time0: f64,
+        time1: f64,
+    ) {
+        if !self.visible {
+            return;
+        }
+        let material = self.material.as_ref().or(inherited);
+
+        let mut contents = Vec::new();
+        match &self.content {
+            Content::Group => {}
+            Content::Object(object) => contents.push(object.as_ref().clone()),
+            Content::Shape(build) => {
+                let material = material.unwrap_or_else(|| {
+                    panic!("The scene node `{}` has no material to inherit.", self.name)
+                });
+                contents.push(build(material.clone()));
+            }
+        }
+        for child in &self.children {
+            child.collect(material, &mut contents, time0, time1);
+        }
+
+        if self.transform == Affine3::IDENTITY {
+            objects.extend(contents);
+        } else if let Some(inner) = Self::combine(contents, time0, time1) {
+            objects.push(Transform::new(inner, self.transform).into());
+        }
+    }
+
+    /// Combine hittables into one, keeping several in a `BVH`.
+    fn combine(mut objects: Vec<Hittable>, time0: f64, time1: f64) -> Option<Hittable> {
+        if objects.len() > 1 {
+            Some(BVH::new(HittableList::from_vec(objects), time0, time1).into())
+        } else {
+            objects.pop()
+        }
+    }
+}
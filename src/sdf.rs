@@ -0,0 +1,307 @@
+use crate::{
+    aabb::AABB,
+    float,
+    hit::{Hit, HitRecord},
+    material::Material,
+    ray::Ray,
+    sphere::Sphere,
+    vec::Vec3,
+};
+
+/// An expression tree for a signed distance function, negative inside the shape.
+///
+/// Shapes are built from primitives centered at the origin and combined with the
+/// operator methods, such as `smooth_union`, `repeat` and `twist`.
+#[derive(Clone, Debug)]
+pub enum Sdf {
+    Sphere {
+        radius: f64,
+    },
+    Cuboid {
+        half_extents: Vec3,
+    },
+    /// A torus around the Y axis.
+    Torus {
+        major_radius: f64,
+        minor_radius: f64,
+    },
+    /// The Mandelbulb fractal with the given power, lying within a radius of about 1.2.
+    Mandelbulb {
+        power: f64,
+        iterations: usize,
+    },
+    Union(Box<Sdf>, Box<Sdf>),
+    Intersection(Box<Sdf>, Box<Sdf>),
+    Difference(Box<Sdf>, Box<Sdf>),
+    /// A union blending the two shapes together over a distance of about `k`.
+    SmoothUnion {
+        a: Box<Sdf>,
+        b: Box<Sdf>,
+        k: f64,
+    },
+    /// The shape grown outward by `radius`, which rounds its edges.
+    Round {
+        inner: Box<Sdf>,
+        radius: f64,
+    },
+    Translate {
+        inner: Box<Sdf>,
+        offset: Vec3,
+    },
+    Scale {
+        inner: Box<Sdf>,
+        factor: f64,
+    },
+    /// Infinite copies of the shape at multiples of `period`, with a zero period not repeating.
+    Repeat {
+        inner: Box<Sdf>,
+        period: Vec3,
+    },
+    /// The shape twisted around the Y axis by `rate` radians per unit of height.
+    Twist {
+        inner: Box<Sdf>,
+        rate: f64,
+    },
+}
+
+impl Sdf {
+    pub fn sphere(radius: f64) -> Self {
+        Self::Sphere { radius }
+    }
+
+    pub fn cuboid(half_extents: Vec3) -> Self {
+        Self::Cuboid { half_extents }
+    }
+
+    pub fn torus(major_radius: f64, minor_radius: f64) -> Self {
+        Self::Torus { major_radius, minor_radius }
+    }
+
+    pub fn mandelbulb(power: f64, iterations: usize) -> Self {
+        Self::Mandelbulb { power, iterations }
+    }
+
+    pub fn union(self, other: Sdf) -> Self {
+        Self::Union(Box::new(self), Box::new(other))
+    }
+
+    pub fn intersection(self, other: Sdf) -> Self {
+        Self::Intersection(Box::new(self), Box::new(other))
+    }
+
+    pub fn difference(self, other: Sdf) -> Self {
+        Self::Difference(Box::new(self), Box::new(other))
+    }
+
+    pub fn smooth_union(self, other: Sdf, k: f64) -> Self {
+        Self::SmoothUnion { a: Box::new(self), b: Box::new(other), k }
+    }
+
+    pub fn round(self, radius: f64) -> Self {
+        Self::Round { inner: Box::new(self), radius }
+    }
+
+    pub fn translate(self, offset: Vec3) -> Self {
+        Self::Translate { inner: Box::new(self), offset }
+    }
+
+    pub fn scale(self, factor: f64) -> Self {
+        Self::Scale { inner: Box::new(self), factor }
+    }
+
+    pub fn repeat(self, period: Vec3) -> Self {
+        Self::Repeat { inner: Box::new(self), period }
+    }
+
+    pub fn twist(self, rate: f64) -> Self {
+        Self::Twist { inner: Box::new(self), rate }
+    }
+
+    /// Evaluate the signed distance from the point `p` to the surface.
+    ///
+    /// Twisted shapes and fractals only give an estimate of the distance,
+    /// which may overshoot and calls for a smaller step scale when sphere tracing.
+    pub fn distance(&self, p: Vec3) -> f64 {
+        match self {
+            Self::Sphere { radius } => p.length() - radius,
+            Self::Cuboid { half_extents } => {
+                let q = p.abs() - *half_extents;
+                q.max(Vec3::ZERO).length() + q.max_element().min(0.0)
+            }
+            Self::Torus { major_radius, minor_radius } => {
+                let ring = (p.x * p.x + p.z * p.z).sqrt() - major_radius;
+                (ring * ring + p.y * p.y).sqrt() - minor_radius
+            }
+            Self::Mandelbulb { power, iterations } => mandelbulb(p, *power, *iterations),
+            Self::Union(a, b) => a.distance(p).min(b.distance(p)),
+            Self::Intersection(a, b) => a.distance(p).max(b.distance(p)),
+            Self::Difference(a, b) => a.distance(p).max(-b.distance(p)),
+            Self::SmoothUnion { a, b, k } => {
+                let (da, db) = (a.distance(p), b.distance(p));
+                let h = (0.5 + 0.5 * (db - da) / k).clamp(0.0, 1.0);
+                db + (da - db) * h - k * h * (1.0 - h)
+            }
+            Self::Round { inner, radius } => inner.distance(p) - radius,
+            Self::Translate { inner, offset } => inner.distance(p - *offset),
+            Self::Scale { inner, factor } => inner.distance(p / *factor) * factor,
+            Self::Repeat { inner, period } => {
+                let wrap = |x: f64, period: f64| {
+                    if period > 0.0 {
+                        x - period * (x / period).round()
+                    } else {
+                        x
+                    }
+                };
+                let q = Vec3::new(wrap(p.x, period.x), wrap(p.y, period.y), wrap(p.z, period.z));
+                inner.distance(q)
+            }
+            Self::Twist { inner, rate } => {
+                let (sin, cos) = (rate * p.y).sin_cos();
+                inner.distance(Vec3::new(cos * p.x - sin * p.z, p.y, sin * p.x + cos * p.z))
+            }
+        }
+    }
+}
+
+/// Distance estimate for the Mandelbulb, from the running derivative of the iteration.
+fn mandelbulb(p: Vec3, power: f64, iterations: usize) -> f64 {
+    let mut z = p;
+    let mut dr = 1.0;
+    let mut r = z.length();
+
+    for _ in 0..iterations {
+        if r > 2.0 || r == 0.0 {
+            break;
+        }
+
+        // Raise z to the power in spherical coordinates
+        let theta = (z.z / r).acos() * power;
+        let phi = z.y.atan2(z.x) * power;
+        dr = r.powf(power - 1.0) * power * dr + 1.0;
+
+        let zr = r.powf(power);
+        z = zr * Vec3::new(theta.sin() * phi.cos(), theta.sin() * phi.sin(), theta.cos()) + p;
+        r = z.length();
+    }
+
+    if r == 0.0 {
+        return 0.0;
+    }
+    0.5 * r.ln() * r / dr
+}
+
+/// A shape given by a signed distance function, found along rays by sphere tracing.
+///
+/// Distance functions do not bound themselves, so the shape takes a bounding box
+/// that must contain the whole surface. Normals are estimated by central differences
+/// of the distance, and the texture coordinates are the spherical coordinates of the normal.
+///
+/// ```
+/// use raytracing_rust::{
+///     aabb::AABB, color::Color, hit::Hit, material::Lambertian, ray::Ray,
+///     sdf::{DistanceField, Sdf}, vec::Vec3,
+/// };
+///
+/// // Two spheres blended together
+/// let sdf = Sdf::sphere(1.0)
+///     .translate(Vec3::new(-0.8, 0.0, 0.0))
+///     .smooth_union(Sdf::sphere(1.0).translate(Vec3::new(0.8, 0.0, 0.0)), 0.5);
+/// let bbox = AABB::new(Vec3::new(-2.0, -1.5, -1.5), Vec3::new(2.0, 1.5, 1.5));
+/// let blob = DistanceField::new(sdf, bbox, Lambertian::from(Color::new(0.5, 0.5, 0.5)));
+///
+/// let r = Ray::new(Vec3::new(-0.8, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0), 0.0);
+/// let rec = blob.hit(&r, 0.001, f64::INFINITY).unwrap();
+/// assert!((rec.point.z - 1.0).abs() < 1e-3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DistanceField {
+    sdf: Sdf,
+    bbox: AABB,
+    max_steps: usize,
+    epsilon: f64,
+    step_scale: f64,
+    material: Material,
+}
+
+impl DistanceField {
+    pub fn new(sdf: Sdf, bbox: AABB, material: impl Into<Material>) -> Self {
+        Self {
+            sdf,
+            bbox,
+            max_steps: 256,
+            epsilon: 1e-4,
+            step_scale: 1.0,
+            material: material.into(),
+        }
+    }
+
+    /// Set the maximum number of sphere tracing steps before a ray is treated as a miss.
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Set the distance from the surface within which a ray counts as hitting it.
+    pub fn with_epsilon(mut self, epsilon: f64) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// Set the fraction of the distance bound taken with each step,
+    /// which should be below one for distance estimates that may overshoot.
+    pub fn with_step_scale(mut self, step_scale: f64) -> Self {
+        self.step_scale = step_scale;
+        self
+    }
+
+    pub fn sdf(&self) -> &Sdf {
+        &self.sdf
+    }
+
+    /// Estimate the outward normal at a point by central differences of the distance.
+    fn normal(&self, p: Vec3) -> Vec3 {
+        let h = self.epsilon;
+        let d = |offset: Vec3| self.sdf.distance(p + offset) - self.sdf.distance(p - offset);
+        Vec3::new(d(Vec3::new(h, 0.0, 0.0)), d(Vec3::new(0.0, h, 0.0)), d(Vec3::new(0.0, 0.0, h)))
+            .normalize_or_zero()
+    }
+}
+
+impl Hit for DistanceField {
+    fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<HitRecord> {
+        let (s_start, s_end) = self.bbox.clip(r, s_min, s_max)?;
+
+        // March in units of distance, so each step can safely move by the distance bound
+        let length = r.direction().length();
+        let dir = r.direction() / length;
+        let (t_end, mut t) = (s_end * length, s_start * length);
+
+        for _ in 0..self.max_steps {
+            let p = r.origin() + t * dir;
+            let dist = self.sdf.distance(p).abs();
+            if dist < self.epsilon {
+                let s = t / length;
+                let normal = self.normal(p);
+                let (u, v) = Sphere::get_uv(normal);
+
+                // The point is anywhere within epsilon of the surface, and a ray leaving it
+                // must start further than epsilon away not to stop at once
+                let mut rec = HitRecord::new(s, u, v, p, self.material.clone());
+                rec.error = Vec3::splat(2.0 * self.epsilon) + float::gamma(3) * p.abs();
+                rec.set_face_normal(r, normal);
+                return Some(rec);
+            }
+
+            t += dist * self.step_scale;
+            if t > t_end {
+                break;
+            }
+        }
+
+        None
+    }
+
+    fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<AABB> {
+        Some(self.bbox)
+    }
+}
@@ -1,7 +1,9 @@
-use std::f64::consts::PI;
+use std::{f64::consts::PI, ops::Range};
 
 use crate::{
     aabb::AABB,
+    animation::Keyframes,
+    float,
     hit::{Hit, HitRecord},
     material::Material,
     ray::Ray,
@@ -37,6 +39,21 @@ impl Sphere {
         Self::new(center, center, 0.0, 0.0, radius, material)
     }
 
+    /// Create a sphere that follows a keyframed path of centers during the shutter interval.
+    ///
+    /// The motion within the interval is approximated as linear between the keyed positions
+    /// at the shutter open and close times.
+    pub fn keyframed(
+        centers: &Keyframes<Vec3>,
+        shutter: Range<f64>,
+        radius: f64,
+        material: impl Into<Material>,
+    ) -> Self {
+        let center0 = centers.value_at(shutter.start);
+        let center1 = centers.value_at(shutter.end);
+        Self::new(center0, center1, shutter.start, shutter.end, radius, material)
+    }
+
     pub fn center(&self, time: f64) -> Vec3 {
         let dtime = self.time1 - self.time0;
         let dcenter = self.center1 - self.center0;
@@ -47,6 +64,10 @@ impl Sphere {
         }
     }
 
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
     pub fn get_uv(point: Vec3) -> (f64, f64) {
         // Point: a given point on the sphere of radius one, centered at the origin.
         // u: returned value [0,1] of angle around the Y axis from X=-1.
@@ -87,11 +108,13 @@ impl Hit for Sphere {
             }
         }
 
-        let root_point = r.at(s_root);
-        let outward_normal = (root_point - self.center(r.time())) / self.radius;
+        let center = self.center(r.time());
+        let (root_point, error) = float::sphere_point(center, self.radius, r.at(s_root));
+        let outward_normal = (root_point - center) / self.radius;
         let (u, v) = Self::get_uv(outward_normal);
 
         let mut rec = HitRecord::new(s_root, u, v, root_point, self.material.clone());
+        rec.error = error;
         rec.set_face_normal(r, outward_normal);
 
         Some(rec)
@@ -0,0 +1,215 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::hittable::Hittable;
+
+/// Whether event counters are collected, which costs a little on every ray and test,
+/// so they are only compiled in with the `stats` feature.
+pub const COUNTERS_ENABLED: bool = cfg!(feature = "stats");
+
+/// Event counters collected while rendering.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Counter {
+    PrimaryRays,
+    SecondaryRays,
+    BVHNodeVisits,
+    AABBTests,
+    DepthTerminations,
+    Absorptions,
+    Escapes,
+    BVHBuildNanos,
+    RenderNanos,
+}
+
+const NUM_COUNTERS: usize = 9;
+
+/// Counters owned by a single thread.
+///
+/// Only the owning thread writes to a block, so increments are a plain load and store
+/// rather than a locked read-modify-write. Atomics are only needed so other threads can
+/// read the totals. Primitive tests are keyed by variant name, and their lock is only
+/// contended while the totals are read.
+struct CounterBlock {
+    counters: [AtomicU64; NUM_COUNTERS],
+    primitive_tests: Mutex<HashMap<&'static str, u64>>,
+}
+
+impl CounterBlock {
+    fn new() -> Self {
+        Self { counters: Default::default(), primitive_tests: Default::default() }
+    }
+
+    fn reset(&self) {
+        self.counters.iter().for_each(|c| c.store(0, Ordering::Relaxed));
+        self.primitive_tests.lock().unwrap().clear();
+    }
+}
+
+static REGISTRY: Mutex<Vec<Arc<CounterBlock>>> = Mutex::new(Vec::new());
+
+thread_local! {
+    static LOCAL: Arc<CounterBlock> = {
+        let block = Arc::new(CounterBlock::new());
+        REGISTRY.lock().unwrap().push(block.clone());
+        block
+    };
+}
+
+fn bump(counter: &AtomicU64, amount: u64) {
+    counter.store(counter.load(Ordering::Relaxed) + amount, Ordering::Relaxed);
+}
+
+/// Increment an event counter for the current thread, if counters are enabled.
+pub fn increment(counter: Counter) {
+    if COUNTERS_ENABLED {
+        LOCAL.with(|block| bump(&block.counters[counter as usize], 1));
+    }
+}
+
+/// Record an intersection test against a `Hittable`, if counters are enabled.
+///
+/// Only primitives are counted, not the wrappers and aggregates passing the ray on to them.
+pub fn record_primitive_test(object: &Hittable) {
+    if COUNTERS_ENABLED && object.is_primitive() {
+        LOCAL.with(|block| {
+            *block.primitive_tests.lock().unwrap().entry(object.variant_name()).or_insert(0) += 1
+        });
+    }
+}
+
+/// Add the elapsed time since `start` to a timing counter.
+///
+/// Timings are recorded once per build or render, so they are kept without the `stats` feature.
+pub fn record_time(counter: Counter, start: Instant) {
+    let nanos = start.elapsed().as_nanos() as u64;
+    LOCAL.with(|block| bump(&block.counters[counter as usize], nanos));
+}
+
+/// Reset the counters of all threads to zero.
+pub fn reset() {
+    REGISTRY.lock().unwrap().iter().for_each(|block| block.reset());
+}
+
+/// Snapshot of the counters summed over all threads.
+#[derive(Clone, Debug)]
+pub struct RenderStats {
+    counters: [u64; NUM_COUNTERS],
+    primitive_tests: BTreeMap<&'static str, u64>,
+}
+
+impl RenderStats {
+    /// Collect the current totals of all counters.
+    pub fn collect() -> Self {
+        let mut counters = [0; NUM_COUNTERS];
+        let mut primitive_tests = BTreeMap::new();
+
+        for block in REGISTRY.lock().unwrap().iter() {
+            for (total, c) in counters.iter_mut().zip(&block.counters) {
+                *total += c.load(Ordering::Relaxed);
+            }
+            for (&name, &count) in block.primitive_tests.lock().unwrap().iter() {
+                *primitive_tests.entry(name).or_insert(0) += count;
+            }
+        }
+
+        Self { counters, primitive_tests }
+    }
+
+    pub fn get(&self, counter: Counter) -> u64 {
+        self.counters[counter as usize]
+    }
+
+    /// Intersection tests per primitive `Hittable` variant name, for the variants
+    /// that were tested, in order of name.
+    pub fn primitive_tests(&self) -> impl Iterator<Item = (&'static str, u64)> + '_ {
+        self.primitive_tests.iter().map(|(&name, &count)| (name, count))
+    }
+
+    /// Average number of ray segments traced per primary ray.
+    pub fn average_path_length(&self) -> f64 {
+        let primary = self.get(Counter::PrimaryRays);
+        if primary == 0 {
+            return 0.0;
+        }
+        (primary + self.get(Counter::SecondaryRays)) as f64 / primary as f64
+    }
+
+    pub fn bvh_build_time(&self) -> Duration {
+        Duration::from_nanos(self.get(Counter::BVHBuildNanos))
+    }
+
+    pub fn render_time(&self) -> Duration {
+        Duration::from_nanos(self.get(Counter::RenderNanos))
+    }
+
+    /// Format the statistics as a JSON object.
+    pub fn to_json(&self) -> String {
+        let primitive_tests: Vec<String> = self
+            .primitive_tests()
+            .map(|(name, count)| format!("\"{}\": {}", name, count))
+            .collect();
+
+        format!(
+            concat!(
+                "{{\"primary_rays\": {}, \"secondary_rays\": {}, \"bvh_node_visits\": {}, ",
+                "\"aabb_tests\": {}, \"primitive_tests\": {{{}}}, \"average_path_length\": {}, ",
+                "\"terminations\": {{\"depth\": {}, \"absorption\": {}, \"escape\": {}}}, ",
+                "\"bvh_build_seconds\": {}, \"render_seconds\": {}}}"
+            ),
+            self.get(Counter::PrimaryRays),
+            self.get(Counter::SecondaryRays),
+            self.get(Counter::BVHNodeVisits),
+            self.get(Counter::AABBTests),
+            primitive_tests.join(", "),
+            self.average_path_length(),
+            self.get(Counter::DepthTerminations),
+            self.get(Counter::Absorptions),
+            self.get(Counter::Escapes),
+            self.bvh_build_time().as_secs_f64(),
+            self.render_time().as_secs_f64(),
+        )
+    }
+}
+
+impl fmt::Display for RenderStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let row = |f: &mut fmt::Formatter<'_>, name: &str, value: &dyn fmt::Display| {
+            writeln!(f, "  {:<28} {:>16}", name, value)
+        };
+
+        writeln!(f, "Render statistics")?;
+        if COUNTERS_ENABLED {
+            row(f, "Primary rays", &self.get(Counter::PrimaryRays))?;
+            row(f, "Secondary rays", &self.get(Counter::SecondaryRays))?;
+            row(f, "Average path length", &format!("{:.3}", self.average_path_length()))?;
+            row(f, "BVH node visits", &self.get(Counter::BVHNodeVisits))?;
+            row(f, "AABB tests", &self.get(Counter::AABBTests))?;
+
+            writeln!(f, "Intersection tests")?;
+            for (name, count) in self.primitive_tests() {
+                row(f, name, &count)?;
+            }
+
+            writeln!(f, "Path terminations")?;
+            row(f, "Depth limit", &self.get(Counter::DepthTerminations))?;
+            row(f, "Absorption", &self.get(Counter::Absorptions))?;
+            row(f, "Escape", &self.get(Counter::Escapes))?;
+        } else {
+            writeln!(
+                f,
+                "  Event counters are off; build with `--features stats` to collect them."
+            )?;
+        }
+
+        writeln!(f, "Timing")?;
+        row(f, "BVH build (s)", &format!("{:.3}", self.bvh_build_time().as_secs_f64()))?;
+        row(f, "Render (s)", &format!("{:.3}", self.render_time().as_secs_f64()))
+    }
+}
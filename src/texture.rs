@@ -1,17 +1,25 @@
 use image::GenericImageView;
 
-use crate::{color::Color, perlin::Perlin, vec::Vec3};
+use crate::{color::Color, hit::HitRecord, perlin::Perlin, vec::Vec3};
 
+/// Trait for looking up the color of a texture at a surface point.
 pub trait TextureColor {
     fn color_value(&self, point: Vec3, u: f64, v: f64) -> Color;
+
+    /// Get the texture color at a ray hit, which by default looks up the hit point and UVs.
+    fn hit_color(&self, rec: &HitRecord) -> Color {
+        self.color_value(rec.point, rec.u, rec.v)
+    }
 }
 
+/// Enumeration of textures that can color a material.
 #[derive(Clone, Debug)]
 pub enum Texture {
     Solid(SolidColor),
     Checker(CheckerTexture),
     Noise(NoiseTexture),
     Image(ImageTexture),
+    VertexColor(VertexColorTexture),
 }
 
 impl From<SolidColor> for Texture {
@@ -38,6 +46,12 @@ impl From<ImageTexture> for Texture {
     }
 }
 
+impl From<VertexColorTexture> for Texture {
+    fn from(texture: VertexColorTexture) -> Self {
+        Self::VertexColor(texture)
+    }
+}
+
 impl TextureColor for Texture {
     fn color_value(&self, point: Vec3, u: f64, v: f64) -> Color {
         match self {
@@ -45,10 +59,22 @@ impl TextureColor for Texture {
             Self::Checker(inner) => inner.color_value(point, u, v),
             Self::Noise(inner) => inner.color_value(point, u, v),
             Self::Image(inner) => inner.color_value(point, u, v),
+            Self::VertexColor(inner) => inner.color_value(point, u, v),
+        }
+    }
+
+    fn hit_color(&self, rec: &HitRecord) -> Color {
+        match self {
+            Self::Solid(inner) => inner.hit_color(rec),
+            Self::Checker(inner) => inner.hit_color(rec),
+            Self::Noise(inner) => inner.hit_color(rec),
+            Self::Image(inner) => inner.hit_color(rec),
+            Self::VertexColor(inner) => inner.hit_color(rec),
         }
     }
 }
 
+/// A texture with a single constant color.
 #[derive(Clone, Copy, Debug)]
 pub struct SolidColor {
     color: Color,
@@ -66,6 +92,7 @@ impl TextureColor for SolidColor {
     }
 }
 
+/// A 3D checker pattern alternating between two textures.
 #[derive(Clone, Debug)]
 pub struct CheckerTexture {
     even: Box<Texture>,
@@ -93,6 +120,7 @@ impl TextureColor for CheckerTexture {
     }
 }
 
+/// A marble-like texture driven by Perlin turbulence.
 #[derive(Clone, Debug)]
 pub struct NoiseTexture {
     noise: Perlin,
@@ -112,6 +140,7 @@ impl TextureColor for NoiseTexture {
     }
 }
 
+/// A texture mapped from an image file using the surface UV coordinates.
 #[derive(Clone, Debug)]
 pub struct ImageTexture {
     data: Vec<u8>,
@@ -123,8 +152,9 @@ impl ImageTexture {
     pub fn new(filepath: &str) -> Self {
         match image::open(filepath) {
             Ok(img) => {
+                // Convert to 8-bit RGB, since lookups assume three bytes per pixel
                 let (width, height) = img.dimensions();
-                let data = img.into_bytes();
+                let data = img.into_rgb8().into_raw();
                 Self { data, width: width as usize, height: height as usize }
             }
             Err(_) => Self { data: Vec::new(), width: 0, height: 0 },
@@ -150,3 +180,27 @@ impl TextureColor for ImageTexture {
         Color::new(r, g, b)
     }
 }
+
+/// A texture that shows the per-vertex colors of the object that was hit.
+///
+/// Objects without vertex colors are shown with the fallback texture instead.
+#[derive(Clone, Debug)]
+pub struct VertexColorTexture {
+    fallback: Box<Texture>,
+}
+
+impl VertexColorTexture {
+    pub fn new(fallback: impl Into<Texture>) -> Self {
+        Self { fallback: Box::new(fallback.into()) }
+    }
+}
+
+impl TextureColor for VertexColorTexture {
+    fn color_value(&self, point: Vec3, u: f64, v: f64) -> Color {
+        self.fallback.color_value(point, u, v)
+    }
+
+    fn hit_color(&self, rec: &HitRecord) -> Color {
+        rec.vertex_color.unwrap_or_else(|| self.fallback.hit_color(rec))
+    }
+}
@@ -0,0 +1,147 @@
+use std::f64::consts::PI;
+
+use crate::{
+    aabb::AABB,
+    disk::azimuth,
+    float,
+    hit::{Hit, HitRecord},
+    material::Material,
+    onb::ONB,
+    ray::Ray,
+    roots::{solve_quadratic, solve_quartic},
+    vec::Vec3,
+};
+
+/// A torus around an axis through its center, with a tube of radius `minor_radius`
+/// swept along a circle of radius `major_radius`.
+///
+/// The texture coordinate `u` is the angle around the axis and `v` the angle around the tube.
+///
+/// ```
+/// use raytracing_rust::{color::Color, hit::Hit, material::Lambertian, ray::Ray, torus::Torus, vec::Vec3};
+///
+/// let torus = Torus::new(
+///     Vec3::new(0.0, 0.0, 0.0),
+///     Vec3::new(0.0, 1.0, 0.0),
+///     2.0,
+///     0.5,
+///     Lambertian::from(Color::new(0.5, 0.5, 0.5)),
+/// );
+///
+/// // Straight down through the hole, and then down onto the tube
+/// let r = Ray::new(Vec3::new(0.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0), 0.0);
+/// assert!(torus.hit(&r, 0.001, f64::INFINITY).is_none());
+/// let r = Ray::new(Vec3::new(2.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0), 0.0);
+/// let rec = torus.hit(&r, 0.001, f64::INFINITY).unwrap();
+/// assert!((rec.point.y - 0.5).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Torus {
+    center: Vec3,
+    onb: ONB,
+    major_radius: f64,
+    minor_radius: f64,
+    material: Material,
+}
+
+impl Torus {
+    pub fn new(
+        center: Vec3,
+        axis: Vec3,
+        major_radius: f64,
+        minor_radius: f64,
+        material: impl Into<Material>,
+    ) -> Self {
+        Self {
+            center,
+            onb: ONB::from_w(axis),
+            major_radius,
+            minor_radius,
+            material: material.into(),
+        }
+    }
+
+    pub fn center(&self) -> Vec3 {
+        self.center
+    }
+
+    pub fn axis(&self) -> Vec3 {
+        self.onb.w()
+    }
+
+    pub fn major_radius(&self) -> f64 {
+        self.major_radius
+    }
+
+    pub fn minor_radius(&self) -> f64 {
+        self.minor_radius
+    }
+}
+
+impl Hit for Torus {
+    fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<HitRecord> {
+        let (big_r, small_r) = (self.major_radius, self.minor_radius);
+
+        // Work with a unit direction, so the quartic is in terms of distance along the ray
+        let length = r.direction().length();
+        let dir = self.onb.to_local(r.direction()) / length;
+        let origin = self.onb.to_local(r.origin() - self.center);
+
+        // Clip the ray to the bounding sphere first, and solve from where it enters
+        // to keep the coefficients of the quartic small
+        let bound = big_r + small_r;
+        let (t0, t1) =
+            solve_quadratic(1.0, origin.dot(dir), origin.length_squared() - bound * bound)?;
+        let t_min = t0.max(s_min * length);
+        let t_max = t1.min(s_max * length);
+        if t_min > t_max {
+            return None;
+        }
+        let o = origin + t_min * dir;
+
+        // Expand (|p|^2 + R^2 - r^2)^2 = 4 R^2 (x^2 + y^2) along the ray p = o + t dir
+        let b = o.dot(dir);
+        let k = o.length_squared() + big_r * big_r - small_r * small_r;
+        let four_r2 = 4.0 * big_r * big_r;
+        let coeffs = [
+            1.0,
+            4.0 * b,
+            4.0 * b * b + 2.0 * k - four_r2 * (dir.x * dir.x + dir.y * dir.y),
+            4.0 * b * k - 2.0 * four_r2 * (o.x * dir.x + o.y * dir.y),
+            k * k - four_r2 * (o.x * o.x + o.y * o.y),
+        ];
+        let t = t_min + *solve_quartic(coeffs, 0.0..t_max - t_min).first()?;
+        let s = t / length;
+
+        // The normal points away from the nearest point on the circle through the tube
+        let p = origin + t * dir;
+        let ring = big_r * Vec3::new(p.x, p.y, 0.0).normalize_or_zero();
+        let outward_normal = (p - ring).normalize();
+
+        let rho = p.truncate().length();
+        let u = azimuth(p);
+        let v = (p.z.atan2(rho - big_r) + PI) / (2.0 * PI);
+
+        // Move the point onto the tube along the normal, whatever the error of the quartic root
+        let local = ring + small_r * outward_normal;
+        let (point, error) = float::frame_point(&self.onb, self.center, local, 7);
+
+        let mut rec = HitRecord::new(s, u, v, point, self.material.clone());
+        rec.error = error;
+        rec.set_face_normal(r, self.onb.to_world(outward_normal));
+
+        Some(rec)
+    }
+
+    fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<AABB> {
+        // The torus is a disk of the major radius swept by a sphere of the minor radius
+        let n = self.onb.w();
+        let extent = Vec3::new(
+            (1.0 - n.x * n.x).max(0.0).sqrt(),
+            (1.0 - n.y * n.y).max(0.0).sqrt(),
+            (1.0 - n.z * n.z).max(0.0).sqrt(),
+        ) * self.major_radius
+            + Vec3::splat(self.minor_radius);
+        Some(AABB::new(self.center - extent, self.center + extent))
+    }
+}
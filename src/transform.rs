@@ -0,0 +1,179 @@
+use crate::{
+    aabb::AABB,
+    hit::{Hit, HitRecord, Interval},
+    hittable::Hittable,
+    ray::Ray,
+    vec::{Affine3, Mat3, Mat4, Quat, Vec3},
+};
+
+/// An affine map from an object's own space into world space, with the inverse and
+/// normal matrix needed to move rays into object space and hits back out of it.
+#[derive(Clone, Debug)]
+pub(crate) struct AffineMap {
+    transform: Affine3,
+    inverse: Affine3,
+    normal_matrix: Mat3,
+}
+
+impl AffineMap {
+    pub(crate) fn new(transform: Affine3) -> Self {
+        let inverse = transform.inverse();
+        let normal_matrix = inverse.matrix3.transpose();
+        Self { transform, inverse, normal_matrix }
+    }
+
+    pub(crate) fn transform(&self) -> &Affine3 {
+        &self.transform
+    }
+
+    /// Move a ray into object space, keeping its contour positions unchanged.
+    pub(crate) fn to_object(&self, r: &Ray) -> Ray {
+        Ray::new(
+            self.inverse.transform_point3(r.origin()),
+            self.inverse.transform_vector3(r.direction()),
+            r.time(),
+        )
+    }
+
+    /// Move a hit record found in object space back into world space.
+    pub(crate) fn to_world(&self, mut rec: HitRecord) -> HitRecord {
+        // Contour positions are shared, so a normal facing the ray in object space
+        // still faces it in world space
+        rec.transform_point(&self.transform);
+        rec.transform_frame(|n| self.normal_matrix * n, |t| self.transform.matrix3 * t);
+        rec
+    }
+
+    /// Move the intervals of a ray found in object space back into world space.
+    pub(crate) fn intervals_to_world(&self, intervals: Vec<Interval>) -> Vec<Interval> {
+        intervals
+            .into_iter()
+            .map(|interval| Interval {
+                enter: interval.enter.map(|rec| self.to_world(rec)),
+                exit: interval.exit.map(|rec| self.to_world(rec)),
+            })
+            .collect()
+    }
+}
+
+/// An object under a general affine transform, such as a rotation about any axis,
+/// a non-uniform scale or a shear.
+///
+/// Transforms wrapping other transforms are folded into a single matrix, so
+/// chaining constructors costs no more per ray than one transform. Normals are
+/// transformed by the inverse transpose, so they stay perpendicular to a stretched surface.
+/// Angles are in degrees, like those of `RotateY`.
+///
+/// ```
+/// use raytracing_rust::{
+///     color::Color, hit::Hit, material::Lambertian, ray::Ray, sphere::Sphere,
+///     transform::Transform, vec::Vec3,
+/// };
+///
+/// // Squash a unit sphere into an ellipsoid, then tip it over and move it up
+/// let ball = Sphere::stationary(Vec3::ZERO, 1.0, Lambertian::from(Color::new(0.5, 0.5, 0.5)));
+/// let ellipsoid = Transform::scale(ball, Vec3::new(1.0, 0.5, 1.0));
+/// let tipped = Transform::rotate_z(ellipsoid, 90.0);
+/// let moved = Transform::translate(tipped, Vec3::new(0.0, 2.0, 0.0));
+///
+/// // The short axis now points along X
+/// let r = Ray::new(Vec3::new(5.0, 2.0, 0.0), Vec3::new(-1.0, 0.0, 0.0), 0.0);
+/// let rec = moved.hit(&r, 0.0, f64::INFINITY).unwrap();
+/// assert!((rec.point.x - 0.5).abs() < 1e-9 && (rec.normal.x - 1.0).abs() < 1e-9);
+///
+/// let bbox = moved.bounding_box(0.0, 1.0).unwrap();
+/// assert!((bbox.max().x - 0.5).abs() < 1e-9 && (bbox.max().y - 3.0).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Transform {
+    object: Box<Hittable>,
+    map: AffineMap,
+}
+
+impl Transform {
+    /// Apply `transform` to the object, mapping its own space into world space.
+    pub fn new(object: impl Into<Hittable>, transform: Affine3) -> Self {
+        match object.into() {
+            Hittable::Transform(inner) => {
+                let map = AffineMap::new(transform * *inner.transform());
+                Self { object: inner.object, map }
+            }
+            object => Self { object: Box::new(object), map: AffineMap::new(transform) },
+        }
+    }
+
+    /// Apply the affine transform of a 4x4 matrix, whose bottom row must be `(0, 0, 0, 1)`.
+    pub fn from_matrix(object: impl Into<Hittable>, matrix: Mat4) -> Self {
+        Self::new(object, Affine3::from_mat4(matrix))
+    }
+
+    pub fn translate(object: impl Into<Hittable>, offset: Vec3) -> Self {
+        Self::new(object, Affine3::from_translation(offset))
+    }
+
+    /// Scale the object about the origin by a factor along each axis.
+    pub fn scale(object: impl Into<Hittable>, scale: Vec3) -> Self {
+        Self::new(object, Affine3::from_scale(scale))
+    }
+
+    pub fn rotate_x(object: impl Into<Hittable>, angle: f64) -> Self {
+        Self::new(object, Affine3::from_rotation_x(angle.to_radians()))
+    }
+
+    pub fn rotate_y(object: impl Into<Hittable>, angle: f64) -> Self {
+        Self::new(object, Affine3::from_rotation_y(angle.to_radians()))
+    }
+
+    pub fn rotate_z(object: impl Into<Hittable>, angle: f64) -> Self {
+        Self::new(object, Affine3::from_rotation_z(angle.to_radians()))
+    }
+
+    /// Rotate the object about `axis` through the origin, counterclockwise looking down the axis.
+    pub fn rotate_axis(object: impl Into<Hittable>, axis: Vec3, angle: f64) -> Self {
+        Self::new(object, Affine3::from_axis_angle(axis.normalize(), angle.to_radians()))
+    }
+
+    /// Rotate the object by Euler angles about the X, Y and Z axes, in that order.
+    pub fn rotate_euler(object: impl Into<Hittable>, angles: Vec3) -> Self {
+        let rotation = Quat::from_rotation_z(angles.z.to_radians())
+            * Quat::from_rotation_y(angles.y.to_radians())
+            * Quat::from_rotation_x(angles.x.to_radians());
+        Self::rotate_quat(object, rotation)
+    }
+
+    pub fn rotate_quat(object: impl Into<Hittable>, rotation: Quat) -> Self {
+        Self::new(object, Affine3::from_quat(rotation.normalize()))
+    }
+
+    /// Place the object at `position`, turned so its +Z axis points at `target`
+    /// and its +Y axis leans towards `up`.
+    pub fn look_at(object: impl Into<Hittable>, position: Vec3, target: Vec3, up: Vec3) -> Self {
+        let w = (target - position).normalize();
+        let u = up.cross(w).normalize();
+        let v = w.cross(u);
+        Self::new(object, Affine3::from_mat3_translation(Mat3::from_cols(u, v, w), position))
+    }
+
+    pub fn object(&self) -> &Hittable {
+        &self.object
+    }
+
+    pub fn transform(&self) -> &Affine3 {
+        self.map.transform()
+    }
+}
+
+impl Hit for Transform {
+    fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<HitRecord> {
+        let rec = self.object.hit(&self.map.to_object(r), s_min, s_max)?;
+        Some(self.map.to_world(rec))
+    }
+
+    fn bounding_box(&self, time0: f64, time1: f64) -> Option<AABB> {
+        self.object.bounding_box(time0, time1).map(|bbox| bbox.transformed(self.transform()))
+    }
+
+    fn intervals(&self, r: &Ray) -> Vec<Interval> {
+        self.map.intervals_to_world(self.object.intervals(&self.map.to_object(r)))
+    }
+}
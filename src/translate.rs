@@ -3,9 +3,10 @@ use crate::{
     hit::{Hit, HitRecord},
     hittable::Hittable,
     ray::Ray,
-    vec::Vec3,
+    vec::{Affine3, Vec3},
 };
 
+/// An object moved by a constant offset.
 #[derive(Clone, Debug)]
 pub struct Translate {
     object: Box<Hittable>,
@@ -23,8 +24,7 @@ impl Hit for Translate {
         let moved_ray = Ray::new(r.origin() - self.offset, r.direction(), r.time());
 
         if let Some(mut rec) = self.object.hit(&moved_ray, s_min, s_max) {
-            rec.point += self.offset;
-            rec.set_face_normal(&moved_ray, rec.normal);
+            rec.transform_point(&Affine3::from_translation(self.offset));
             Some(rec)
         } else {
             None
@@ -0,0 +1,201 @@
+use crate::{
+    aabb::AABB,
+    float,
+    hit::{Hit, HitRecord},
+    material::Material,
+    ray::Ray,
+    vec::Vec3,
+};
+
+/// A triangle with optional per-vertex texture coordinates and normals.
+///
+/// Without texture coordinates, the reported `u` and `v` are the barycentric
+/// weights of the second and third vertices.
+///
+/// ```
+/// use raytracing_rust::{
+///     color::Color, hit::Hit, material::Lambertian, ray::Ray, triangle::Triangle, vec::Vec3,
+/// };
+///
+/// let triangle = Triangle::new(
+///     Vec3::new(0.0, 0.0, 0.0),
+///     Vec3::new(1.0, 0.0, 0.0),
+///     Vec3::new(0.0, 1.0, 0.0),
+///     Lambertian::from(Color::new(0.5, 0.5, 0.5)),
+/// )
+/// .with_uvs([(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]);
+///
+/// let r = Ray::new(Vec3::new(0.25, 0.5, 1.0), Vec3::new(0.0, 0.0, -1.0), 0.0);
+/// let rec = triangle.hit(&r, 0.001, f64::INFINITY).unwrap();
+/// assert!((rec.u - 0.25).abs() < 1e-9 && (rec.v - 0.5).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Triangle {
+    vertices: [Vec3; 3],
+    uvs: Option<[(f64, f64); 3]>,
+    normals: Option<[Vec3; 3]>,
+    material: Material,
+}
+
+impl Triangle {
+    pub fn new(v0: Vec3, v1: Vec3, v2: Vec3, material: impl Into<Material>) -> Self {
+        Self { vertices: [v0, v1, v2], uvs: None, normals: None, material: material.into() }
+    }
+
+    pub fn with_uvs(mut self, uvs: [(f64, f64); 3]) -> Self {
+        self.uvs = Some(uvs);
+        self
+    }
+
+    pub fn with_normals(mut self, normals: [Vec3; 3]) -> Self {
+        self.normals = Some(normals);
+        self
+    }
+
+    pub fn vertices(&self) -> [Vec3; 3] {
+        self.vertices
+    }
+}
+
+/// Intersect a ray with the triangle `(v0, v1, v2)` using the Möller–Trumbore algorithm.
+///
+/// Returns the contour position of the hit and the barycentric weights `(b1, b2)`
+/// of the second and third vertices.
+pub(crate) fn intersect_triangle(
+    r: &Ray,
+    [v0, v1, v2]: [Vec3; 3],
+    s_min: f64,
+    s_max: f64,
+) -> Option<(f64, f64, f64)> {
+    let edge1 = v1 - v0;
+    let edge2 = v2 - v0;
+    let pvec = r.direction().cross(edge2);
+    let det = edge1.dot(pvec);
+
+    // The ray is parallel to the plane of the triangle, or too close to it for the
+    // sign of the determinant to survive rounding, whatever the scale of the scene
+    if det.abs() <= float::gamma(3) * edge1.length() * pvec.length() {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+
+    let tvec = r.origin() - v0;
+    let b1 = tvec.dot(pvec) * inv_det;
+    if !(0.0..=1.0).contains(&b1) {
+        return None;
+    }
+
+    let qvec = tvec.cross(edge1);
+    let b2 = r.direction().dot(qvec) * inv_det;
+    if b2 < 0.0 || b1 + b2 > 1.0 {
+        return None;
+    }
+
+    let s = edge2.dot(qvec) * inv_det;
+    if s < s_min || s > s_max {
+        return None;
+    }
+
+    Some((s, b1, b2))
+}
+
+/// Set the point, normals and shading frame of a hit on the triangle `(v0, v1, v2)`
+/// at barycentric weights `(b1, b2)`, smoothing the shading normal between any vertex normals.
+///
+/// The point is rebuilt from the weights, which keeps it in the plane of the triangle
+/// with a small error bound, however far along the ray the hit is.
+///
+/// The geometric normal is turned to agree with the vertex normals, so the winding order
+/// does not matter when they are given, and the tangent follows increasing `u`.
+pub(crate) fn set_triangle_frame(
+    rec: &mut HitRecord,
+    r: &Ray,
+    [v0, v1, v2]: [Vec3; 3],
+    normals: Option<[Vec3; 3]>,
+    uvs: Option<[(f64, f64); 3]>,
+    (b1, b2): (f64, f64),
+) {
+    let (edge1, edge2) = (v1 - v0, v2 - v0);
+    let geometric_normal = edge1.cross(edge2).normalize();
+
+    rec.point = v0 + b1 * edge1 + b2 * edge2;
+    rec.error = float::gamma(7)
+        * (v0.abs() + b1.abs() * (v0.abs() + v1.abs()) + b2.abs() * (v0.abs() + v2.abs()));
+
+    // Without texture coordinates, u runs along the first edge
+    let tangent = match uvs {
+        Some([uv0, uv1, uv2]) => {
+            let (du1, dv1) = (uv1.0 - uv0.0, uv1.1 - uv0.1);
+            let (du2, dv2) = (uv2.0 - uv0.0, uv2.1 - uv0.1);
+            let det = du1 * dv2 - du2 * dv1;
+            if det.abs() > 1e-12 {
+                (dv2 * edge1 - dv1 * edge2) / det
+            } else {
+                edge1
+            }
+        }
+        None => edge1,
+    };
+
+    match normals {
+        Some([n0, n1, n2]) => {
+            let b0 = 1.0 - b1 - b2;
+            let shading_normal = (b0 * n0 + b1 * n1 + b2 * n2).normalize();
+            let geometric_normal = if geometric_normal.dot(shading_normal) < 0.0 {
+                -geometric_normal
+            } else {
+                geometric_normal
+            };
+            rec.set_face_normal(r, geometric_normal);
+            rec.set_shading_normal(shading_normal);
+        }
+        None => rec.set_face_normal(r, geometric_normal),
+    }
+
+    rec.set_tangent(tangent);
+}
+
+/// Get a bounding box around a set of points,
+/// padded along any axis where the points are nearly coplanar.
+pub(crate) fn padded_bounding_box(points: &[Vec3]) -> AABB {
+    const MIN_EXTENT: f64 = 1e-4;
+
+    let mut min = Vec3::splat(f64::INFINITY);
+    let mut max = Vec3::splat(f64::NEG_INFINITY);
+    for p in points {
+        min = min.min(*p);
+        max = max.max(*p);
+    }
+
+    for i in 0..3 {
+        if max[i] - min[i] < MIN_EXTENT {
+            min[i] -= 0.5 * MIN_EXTENT;
+            max[i] += 0.5 * MIN_EXTENT;
+        }
+    }
+
+    AABB::new(min, max)
+}
+
+impl Hit for Triangle {
+    fn hit(&self, r: &Ray, s_min: f64, s_max: f64) -> Option<HitRecord> {
+        let (s, b1, b2) = intersect_triangle(r, self.vertices, s_min, s_max)?;
+        let b0 = 1.0 - b1 - b2;
+
+        let (u, v) = match self.uvs {
+            Some([uv0, uv1, uv2]) => {
+                (b0 * uv0.0 + b1 * uv1.0 + b2 * uv2.0, b0 * uv0.1 + b1 * uv1.1 + b2 * uv2.1)
+            }
+            None => (b1, b2),
+        };
+
+        let mut rec = HitRecord::new(s, u, v, r.at(s), self.material.clone());
+        set_triangle_frame(&mut rec, r, self.vertices, self.normals, self.uvs, (b1, b2));
+
+        Some(rec)
+    }
+
+    fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<AABB> {
+        Some(padded_bounding_box(&self.vertices))
+    }
+}
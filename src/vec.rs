@@ -1,10 +1,23 @@
 use std::ops::Range;
 
-use glam::DVec3;
+use glam::{DAffine3, DMat3, DMat4, DQuat, DVec3};
 use rand::Rng;
 
+/// Type alias for a double-precision 3D vector.
 pub type Vec3 = DVec3;
 
+/// Type alias for a double-precision 3x3 matrix.
+pub type Mat3 = DMat3;
+
+/// Type alias for a double-precision 4x4 matrix.
+pub type Mat4 = DMat4;
+
+/// Type alias for a double-precision rotation quaternion.
+pub type Quat = DQuat;
+
+/// Type alias for a double-precision 3D affine transform.
+pub type Affine3 = DAffine3;
+
 /// Helper functions for generating 3D vectors.
 pub trait VecOps {
     fn random(range: Range<f64>) -> Self;